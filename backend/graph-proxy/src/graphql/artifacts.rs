@@ -0,0 +1,794 @@
+use super::{
+    errors::{error_status, CodedError},
+    workflows::{
+        artifact_filename, artifact_key, artifact_mime_type, fetch_workflow_from_argo_api, Workflow,
+    },
+    VisitInput, CLIENT,
+};
+use crate::{
+    graphql::auth_guard::AuthGuard,
+    s3client::ArtifactUrlArgs,
+    validate_token::{TokenValidator, ValidatedAuthToken, ValidationMethod},
+    ArgoServerUrl, S3Bucket,
+};
+use anyhow::anyhow;
+use async_graphql::{Context, Object, SimpleObject};
+use async_zip::{tokio::write::ZipFileWriter, Compression, ZipEntryBuilder};
+use aws_sdk_s3::{presigning::PresigningConfig, Client};
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use axum_extra::{
+    headers::{authorization::Bearer, Authorization},
+    TypedHeader,
+};
+use chrono::{DateTime, Utc};
+use futures_util::AsyncWriteExt;
+use serde::Deserialize;
+use std::{collections::HashSet, ops::Deref, str::FromStr, time::Duration};
+use tokio::io::DuplexStream;
+use tokio_util::io::ReaderStream;
+use tracing::{debug, instrument, warn};
+use url::Url;
+use uuid::Uuid;
+
+/// The size of the in-memory buffer between the zip writer and the response body
+const BUNDLE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Build the URL of an artifact route below the externally reachable URL of the proxy
+pub(super) fn artifact_route_url(proxy_url: &Url, segments: &[&str]) -> Option<Url> {
+    let mut url = proxy_url.clone();
+    url.path_segments_mut()
+        .ok()?
+        .pop_if_empty()
+        .push("artifacts")
+        .extend(segments);
+    Some(url)
+}
+
+/// The key prefix below which input artifacts uploaded to a visit are stored
+pub(super) fn artifact_upload_prefix(url_args: &ArtifactUrlArgs, visit: &VisitInput) -> String {
+    format!(
+        "{}/{visit}/",
+        url_args.artifact_upload_prefix.trim_matches('/')
+    )
+}
+
+/// A presigned URL to which an input artifact may be uploaded
+#[derive(Debug, SimpleObject)]
+struct ArtifactUpload {
+    /// The key of the artifact, to be referenced when submitting a workflow template
+    key: String,
+    /// The URL to which the file should be uploaded with an HTTP PUT request
+    url: Url,
+    /// The time after which the URL can no longer be used
+    expires_at: DateTime<Utc>,
+}
+
+/// Mutations related to artifacts
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactsMutation;
+
+#[Object(guard = "AuthGuard")]
+impl ArtifactsMutation {
+    /// Create a presigned URL for uploading a file as an input artifact of workflows in a visit
+    #[instrument(name = "graph_proxy_create_artifact_upload", skip(self, ctx))]
+    async fn create_artifact_upload(
+        &self,
+        ctx: &Context<'_>,
+        visit: VisitInput,
+        filename: String,
+    ) -> anyhow::Result<ArtifactUpload> {
+        let filename = std::path::Path::new(&filename)
+            .file_name()
+            .and_then(|filename| filename.to_str())
+            .ok_or_else(|| CodedError::bad_input(format!("Invalid file name: {filename}")))?;
+        let s3_client = ctx
+            .data::<Client>()
+            .map_err(|_| anyhow!("s3 client was expected but not present"))?;
+        let s3_bucket = ctx
+            .data::<S3Bucket>()
+            .map_err(|_| anyhow!("s3 bucket was expected but not present"))?;
+        let url_args = ctx
+            .data_opt::<ArtifactUrlArgs>()
+            .cloned()
+            .unwrap_or_default();
+        check_visit_access(ctx, &visit).await?;
+
+        let key = format!(
+            "{}{}/{filename}",
+            artifact_upload_prefix(&url_args, &visit),
+            Uuid::new_v4()
+        );
+        let expires_in = Duration::from_secs(url_args.artifact_presign_expiry);
+        let request = s3_client
+            .put_object()
+            .bucket(s3_bucket.0.clone())
+            .key(&key)
+            .presigned(PresigningConfig::expires_in(expires_in)?)
+            .await?;
+        Ok(ArtifactUpload {
+            key,
+            url: Url::parse(request.uri())?,
+            expires_at: Utc::now() + expires_in,
+        })
+    }
+}
+
+/// Checks the requester may access workflows in the visit, by listing them from the Argo Server
+async fn check_visit_access(ctx: &Context<'_>, visit: &VisitInput) -> anyhow::Result<()> {
+    let mut url = ctx.data_unchecked::<ArgoServerUrl>().deref().to_owned();
+    let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+    url.path_segments_mut()
+        .unwrap()
+        .extend(["api", "v1", "workflows", &visit.to_string()]);
+    url.query_pairs_mut()
+        .append_pair("listOptions.limit", "1")
+        .append_pair("fields", "metadata.resourceVersion");
+    debug!("Checking access to visit at {url}");
+    let mut request = CLIENT.get(url);
+    if let Some(auth_token) = auth_token {
+        request = request.bearer_auth(auth_token.token());
+    }
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(CodedError::from_response(response).await.into());
+    }
+    Ok(())
+}
+
+/// State of the artifact download route
+#[derive(Debug, Clone)]
+pub struct ArtifactRouterState {
+    /// The base URL of the Argo Server used to check access to workflows
+    pub argo_server_url: ArgoServerUrl,
+    /// Client used to retrieve artifacts
+    pub s3_client: Client,
+    /// The bucket in which artifacts are stored
+    pub s3_bucket: S3Bucket,
+    /// Validate bearer tokens
+    pub token_validator: TokenValidator,
+    /// The method used to validate bearer tokens
+    pub validation_method: ValidationMethod,
+}
+
+/// Path parameters identifying an artifact produced by a task
+#[derive(Debug, Deserialize)]
+pub struct ArtifactPath {
+    /// The visit the workflow was run in, e.g. `mg36964-1`
+    visit: String,
+    /// The name of the workflow
+    workflow_name: String,
+    /// The ID of the task which produced the artifact
+    task_id: String,
+    /// The name of the artifact within the task outputs
+    artifact_name: String,
+}
+
+/// Path parameters identifying a workflow whose artifacts are bundled
+#[derive(Debug, Deserialize)]
+pub struct ArtifactBundlePath {
+    /// The visit the workflow was run in, e.g. `mg36964-1`
+    visit: String,
+    /// The name of the workflow
+    workflow_name: String,
+}
+
+/// Query parameters selecting the tasks whose artifacts are bundled
+#[derive(Debug, Default, Deserialize)]
+pub struct ArtifactBundleQuery {
+    /// Comma separated IDs of the tasks to include, all tasks are included if unset
+    tasks: Option<String>,
+}
+
+/// Streams an artifact through the proxy, supporting HTTP range requests
+///
+/// The bearer token must be valid and must permit reading the workflow from the Argo Server,
+/// which restricts downloads to members of the visit.
+pub async fn artifact_handler(
+    State(state): State<ArtifactRouterState>,
+    Path(path): Path<ArtifactPath>,
+    auth_token_header: Option<TypedHeader<Authorization<Bearer>>>,
+    headers: HeaderMap,
+) -> Response {
+    let auth_token = state
+        .token_validator
+        .validate_token(auth_token_header.map(|it| it.0), state.validation_method)
+        .await;
+    stream_artifact(
+        &state.argo_server_url,
+        &state.s3_client,
+        &state.s3_bucket,
+        auth_token,
+        path,
+        headers.get(header::RANGE),
+    )
+    .await
+}
+
+/// Checks access to the workflow and streams the requested range of the artifact from S3
+#[instrument(name = "graph_proxy_stream_artifact", skip(s3_client, auth_token))]
+async fn stream_artifact(
+    argo_server_url: &Url,
+    s3_client: &Client,
+    s3_bucket: &S3Bucket,
+    auth_token: ValidatedAuthToken,
+    path: ArtifactPath,
+    range: Option<&HeaderValue>,
+) -> Response {
+    let workflow = match authorized_workflow(
+        argo_server_url,
+        auth_token,
+        &path.visit,
+        &path.workflow_name,
+    )
+    .await
+    {
+        Ok(workflow) => workflow,
+        Err(response) => return response,
+    };
+    let Some(manifest) = workflow
+        .manifest
+        .status
+        .as_ref()
+        .and_then(|status| status.nodes.get(&path.task_id))
+        .and_then(|node| node.outputs.as_ref())
+        .and_then(|outputs| {
+            outputs
+                .artifacts
+                .iter()
+                .find(|artifact| artifact.name == path.artifact_name)
+        })
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let (Ok(key), Ok(filename), Ok(mime_type)) = (
+        artifact_key(manifest),
+        artifact_filename(manifest),
+        artifact_mime_type(manifest),
+    ) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let mut request = s3_client.get_object().bucket(s3_bucket.0.clone()).key(key);
+    if let Some(range) = range.and_then(|range| range.to_str().ok()) {
+        request = request.range(range);
+    }
+    let object = match request.send().await {
+        Ok(object) => object,
+        Err(err) => {
+            debug!("Artifact could not be retrieved: {err}");
+            return match err
+                .raw_response()
+                .map(|response| response.status().as_u16())
+            {
+                Some(404) => StatusCode::NOT_FOUND,
+                Some(416) => StatusCode::RANGE_NOT_SATISFIABLE,
+                _ => StatusCode::BAD_GATEWAY,
+            }
+            .into_response();
+        }
+    };
+
+    let mut response = Response::builder()
+        .header(header::CONTENT_TYPE, mime_type)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename.replace('"', "")),
+        );
+    if let Some(content_range) = object.content_range {
+        response = response
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_RANGE, content_range);
+    }
+    if let Some(content_length) = object.content_length {
+        response = response.header(header::CONTENT_LENGTH, content_length);
+    }
+    if let Some(etag) = object.e_tag {
+        response = response.header(header::ETAG, etag);
+    }
+    let mut body = object.body;
+    let stream = async_stream::stream! {
+        while let Some(chunk) = body.next().await {
+            yield chunk;
+        }
+    };
+    response
+        .body(Body::from_stream(stream))
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// Streams a zip archive of the artifacts of a workflow through the proxy
+///
+/// Artifacts of every task are included unless a comma separated list of task IDs is given in
+/// the `tasks` query parameter. The bearer token is checked as for single artifacts.
+pub async fn artifact_bundle_handler(
+    State(state): State<ArtifactRouterState>,
+    Path(path): Path<ArtifactBundlePath>,
+    Query(query): Query<ArtifactBundleQuery>,
+    auth_token_header: Option<TypedHeader<Authorization<Bearer>>>,
+) -> Response {
+    let auth_token = state
+        .token_validator
+        .validate_token(auth_token_header.map(|it| it.0), state.validation_method)
+        .await;
+    stream_artifact_bundle(
+        &state.argo_server_url,
+        &state.s3_client,
+        &state.s3_bucket,
+        auth_token,
+        path,
+        query,
+    )
+    .await
+}
+
+/// Checks access to the workflow and streams a zip archive of the selected artifacts from S3
+///
+/// The archive is written into a bounded pipe by a background task as the response is read,
+/// so only a single buffer of the archive is held in memory.
+#[instrument(
+    name = "graph_proxy_stream_artifact_bundle",
+    skip(s3_client, auth_token)
+)]
+async fn stream_artifact_bundle(
+    argo_server_url: &Url,
+    s3_client: &Client,
+    s3_bucket: &S3Bucket,
+    auth_token: ValidatedAuthToken,
+    path: ArtifactBundlePath,
+    query: ArtifactBundleQuery,
+) -> Response {
+    let workflow = match authorized_workflow(
+        argo_server_url,
+        auth_token,
+        &path.visit,
+        &path.workflow_name,
+    )
+    .await
+    {
+        Ok(workflow) => workflow,
+        Err(response) => return response,
+    };
+    let tasks = query
+        .tasks
+        .as_deref()
+        .map(|tasks| tasks.split(',').collect::<HashSet<_>>());
+    let mut entries = workflow
+        .manifest
+        .status
+        .iter()
+        .flat_map(|status| status.nodes.values())
+        .filter(|node| node.type_ == "Pod")
+        .filter(|node| {
+            tasks
+                .as_ref()
+                .is_none_or(|tasks| tasks.contains(node.id.as_str()))
+        })
+        .flat_map(|node| {
+            node.outputs
+                .iter()
+                .flat_map(|outputs| outputs.artifacts.iter())
+                .filter_map(|artifact| {
+                    Some((
+                        format!("{}/{}", node.id, artifact_filename(artifact).ok()?),
+                        artifact_key(artifact).ok()?.to_string(),
+                    ))
+                })
+        })
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    entries.sort();
+
+    let (writer, reader) = tokio::io::duplex(BUNDLE_BUFFER_SIZE);
+    let s3_client = s3_client.clone();
+    let s3_bucket = s3_bucket.clone();
+    tokio::spawn(async move {
+        if let Err(err) = write_artifact_bundle(writer, &s3_client, &s3_bucket, entries).await {
+            warn!("Artifact bundle was cut short: {err}");
+        }
+    });
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.zip\"", path.workflow_name),
+        )
+        .body(Body::from_stream(ReaderStream::new(reader)))
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// Writes a zip archive of the artifacts, named by their entry names, into the pipe
+async fn write_artifact_bundle(
+    writer: DuplexStream,
+    s3_client: &Client,
+    s3_bucket: &S3Bucket,
+    entries: Vec<(String, String)>,
+) -> anyhow::Result<()> {
+    let mut zip = ZipFileWriter::with_tokio(writer);
+    for (name, key) in entries {
+        let mut object = s3_client
+            .get_object()
+            .bucket(s3_bucket.0.clone())
+            .key(key)
+            .send()
+            .await?;
+        // Artifacts are typically compressed already, so are stored as is
+        let mut entry = zip
+            .write_entry_stream(ZipEntryBuilder::new(name.into(), Compression::Stored))
+            .await?;
+        while let Some(chunk) = object.body.next().await {
+            entry.write_all(&chunk?).await?;
+        }
+        entry.close().await?;
+    }
+    zip.close().await?;
+    Ok(())
+}
+
+/// Checks the bearer token and retrieves the workflow from the Argo Server on behalf of the requester
+///
+/// Returns the response to be sent if the requester may not read the workflow.
+async fn authorized_workflow(
+    argo_server_url: &Url,
+    auth_token: ValidatedAuthToken,
+    visit: &str,
+    workflow_name: &str,
+) -> Result<Workflow, Response> {
+    let auth_token = match auth_token {
+        ValidatedAuthToken::Valid(auth_token) => auth_token,
+        ValidatedAuthToken::Failed(err) => {
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, err).into_response())
+        }
+        ValidatedAuthToken::Invalid | ValidatedAuthToken::Missing => {
+            return Err(StatusCode::UNAUTHORIZED.into_response())
+        }
+    };
+    let Ok(visit) = VisitInput::from_str(visit) else {
+        return Err((StatusCode::BAD_REQUEST, "Invalid visit format").into_response());
+    };
+    match fetch_workflow_from_argo_api(
+        argo_server_url,
+        Some(&auth_token),
+        visit,
+        workflow_name,
+        None,
+    )
+    .await
+    {
+        Ok(Some(workflow)) => Ok(workflow),
+        Ok(None) => Err(StatusCode::NOT_FOUND.into_response()),
+        Err(err) => {
+            debug!("Workflow could not be retrieved: {err}");
+            Err(error_status(&err).into_response())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        stream_artifact, stream_artifact_bundle, ArtifactBundlePath, ArtifactBundleQuery,
+        ArtifactPath,
+    };
+    use crate::{
+        graphql::root_schema_builder,
+        s3client::{ArtifactUrlArgs, Client, S3ClientArgs},
+        validate_token::ValidatedAuthToken,
+        ArgoServerUrl, S3Bucket,
+    };
+    use axum::http::{header, HeaderValue, StatusCode};
+    use axum_extra::headers::{authorization::Bearer, Authorization};
+    use rstest::rstest;
+    use std::path::PathBuf;
+    use url::Url;
+
+    /// Build an S3 client against a mock server
+    fn test_s3_client(server: &mockito::Server) -> Client {
+        Client::from(S3ClientArgs {
+            s3_endpoint_url: Some(Url::parse(&server.url()).unwrap()),
+            s3_access_key_id: Some("test-access-key".to_string()),
+            s3_secret_access_key: Some("test-secret-key".to_string()),
+            s3_force_path_style: true,
+            s3_region: Some("us-west-2".to_string()),
+        })
+    }
+
+    /// The path of the artifact produced by the test workflow
+    fn test_path() -> ArtifactPath {
+        ArtifactPath {
+            visit: "mg36964-1".to_string(),
+            workflow_name: "numpy-benchmark-wdkwj".to_string(),
+            task_id: "numpy-benchmark-wdkwj".to_string(),
+            artifact_name: "main-logs".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn artifact_range_is_streamed_from_s3() {
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1/numpy-benchmark-wdkwj")
+            .match_header("authorization", "Bearer test-token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+        let artifact_endpoint = server
+            .mock(
+                "GET",
+                "/test-bucket/numpy-benchmark-wdkwj/numpy-benchmark-wdkwj/main.log",
+            )
+            .match_query(mockito::Matcher::Any)
+            .match_header("range", "bytes=6-10")
+            .with_status(206)
+            .with_header("content-range", "bytes 6-10/11")
+            .with_header("content-length", "5")
+            .with_body("world")
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let s3_client = test_s3_client(&server);
+        let s3_bucket = S3Bucket("test-bucket".to_string());
+        let auth_token =
+            ValidatedAuthToken::Valid(Authorization::<Bearer>::bearer("test-token").unwrap());
+        let range = HeaderValue::from_static("bytes=6-10");
+        let response = stream_artifact(
+            &argo_server_url,
+            &s3_client,
+            &s3_bucket,
+            auth_token,
+            test_path(),
+            Some(&range),
+        )
+        .await;
+
+        workflow_endpoint.assert_async().await;
+        artifact_endpoint.assert_async().await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 6-10/11");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+        assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"world");
+    }
+
+    #[tokio::test]
+    async fn artifact_requires_valid_token() {
+        let server = mockito::Server::new_async().await;
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let s3_client = test_s3_client(&server);
+        let s3_bucket = S3Bucket("test-bucket".to_string());
+        let response = stream_artifact(
+            &argo_server_url,
+            &s3_client,
+            &s3_bucket,
+            ValidatedAuthToken::Missing,
+            test_path(),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(403, r#"{"code":7,"message":"Forbidden"}"#, StatusCode::FORBIDDEN)]
+    #[case(
+        401,
+        r#"{"code":16,"message":"Unauthorized"}"#,
+        StatusCode::UNAUTHORIZED
+    )]
+    #[case(503, "upstream connect error", StatusCode::BAD_GATEWAY)]
+    async fn artifact_of_unretrievable_workflow_reports_cause(
+        #[case] status: usize,
+        #[case] body: &str,
+        #[case] expected_status: StatusCode,
+    ) {
+        let mut server = mockito::Server::new_async().await;
+        let workflow_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1/numpy-benchmark-wdkwj")
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let s3_client = test_s3_client(&server);
+        let s3_bucket = S3Bucket("test-bucket".to_string());
+        let auth_token =
+            ValidatedAuthToken::Valid(Authorization::<Bearer>::bearer("test-token").unwrap());
+        let response = stream_artifact(
+            &argo_server_url,
+            &s3_client,
+            &s3_bucket,
+            auth_token,
+            test_path(),
+            None,
+        )
+        .await;
+
+        workflow_endpoint.assert_async().await;
+        assert_eq!(response.status(), expected_status);
+    }
+
+    #[tokio::test]
+    async fn artifact_bundle_is_streamed_as_zip() {
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1/numpy-benchmark-wdkwj")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+        let artifact_endpoint = server
+            .mock(
+                "GET",
+                "/test-bucket/numpy-benchmark-wdkwj/numpy-benchmark-wdkwj/main.log",
+            )
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body("hello world")
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let s3_client = test_s3_client(&server);
+        let s3_bucket = S3Bucket("test-bucket".to_string());
+        let auth_token =
+            ValidatedAuthToken::Valid(Authorization::<Bearer>::bearer("test-token").unwrap());
+        let path = ArtifactBundlePath {
+            visit: "mg36964-1".to_string(),
+            workflow_name: "numpy-benchmark-wdkwj".to_string(),
+        };
+        let response = stream_artifact_bundle(
+            &argo_server_url,
+            &s3_client,
+            &s3_bucket,
+            auth_token,
+            path,
+            ArtifactBundleQuery::default(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/zip");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        workflow_endpoint.assert_async().await;
+        artifact_endpoint.assert_async().await;
+
+        let zip = async_zip::base::read::mem::ZipFileReader::new(body.to_vec())
+            .await
+            .unwrap();
+        let entries = zip.file().entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].filename().as_str().unwrap(),
+            "numpy-benchmark-wdkwj/main.log"
+        );
+        let mut content = String::new();
+        zip.reader_with_entry(0)
+            .await
+            .unwrap()
+            .read_to_string_checked(&mut content)
+            .await
+            .unwrap();
+        assert_eq!(content, "hello world");
+    }
+
+    #[tokio::test]
+    async fn artifact_bundle_of_unknown_tasks_is_not_found() {
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1/numpy-benchmark-wdkwj")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let s3_client = test_s3_client(&server);
+        let s3_bucket = S3Bucket("test-bucket".to_string());
+        let auth_token =
+            ValidatedAuthToken::Valid(Authorization::<Bearer>::bearer("test-token").unwrap());
+        let path = ArtifactBundlePath {
+            visit: "mg36964-1".to_string(),
+            workflow_name: "numpy-benchmark-wdkwj".to_string(),
+        };
+        let query = ArtifactBundleQuery {
+            tasks: Some("not-a-task".to_string()),
+        };
+        let response = stream_artifact_bundle(
+            &argo_server_url,
+            &s3_client,
+            &s3_bucket,
+            auth_token,
+            path,
+            query,
+        )
+        .await;
+
+        workflow_endpoint.assert_async().await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn create_artifact_upload_mutation() {
+        let mut server = mockito::Server::new_async().await;
+        let visit_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                "1".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"metadata":{},"items":null}"#)
+            .create_async()
+            .await;
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(ValidatedAuthToken::Valid(
+                Authorization::<Bearer>::bearer("test-token").unwrap(),
+            ))
+            .data(test_s3_client(&server))
+            .data(S3Bucket("test-bucket".to_string()))
+            .data(ArtifactUrlArgs {
+                artifact_presign_expiry: 600,
+                ..Default::default()
+            })
+            .finish();
+        let query = r#"
+            mutation {
+                createArtifactUpload(
+                    visit: { proposalCode: "mg", proposalNumber: 36964, number: 1 },
+                    filename: "../model.pdb"
+                ) {
+                    key
+                    url
+                }
+            }
+        "#;
+        let response = schema
+            .execute(query)
+            .await
+            .into_result()
+            .unwrap()
+            .data
+            .into_json()
+            .unwrap();
+
+        visit_endpoint.assert_async().await;
+        let key = response["createArtifactUpload"]["key"].as_str().unwrap();
+        assert!(key.starts_with("uploads/mg36964-1/"));
+        assert!(key.ends_with("/model.pdb"));
+        let url = response["createArtifactUpload"]["url"].as_str().unwrap();
+        assert!(url.starts_with(&format!("{}/test-bucket/{key}", server.url())));
+        assert!(url.contains("X-Amz-Expires=600"));
+    }
+}
@@ -11,6 +11,8 @@ pub struct AuthGuard;
 pub enum AuthErrorCode {
     /// Users bearer token could not be validated
     Unauthenticated,
+    /// User does not hold the role required
+    Forbidden,
 }
 
 impl Guard for AuthGuard {
@@ -42,6 +44,7 @@ impl Display for AuthErrorCode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let value = match self {
             AuthErrorCode::Unauthenticated => "UNAUTHENTICATED",
+            AuthErrorCode::Forbidden => "FORBIDDEN",
         };
         write!(f, "{}", value)
     }
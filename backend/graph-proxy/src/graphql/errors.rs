@@ -0,0 +1,343 @@
+use super::triggers::TriggerError;
+use argo_workflows_openapi::GrpcGatewayRuntimeError;
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute, NextSubscribe},
+    Response, ServerError,
+};
+use futures_util::{stream::BoxStream, StreamExt};
+use reqwest::StatusCode;
+use std::{fmt::Display, sync::Arc};
+
+/// Error codes describing why a request to an upstream service failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// The requested resource does not exist
+    NotFound,
+    /// The upstream service did not accept the credentials of the requester
+    Unauthenticated,
+    /// The requester is not permitted to access the resource
+    Forbidden,
+    /// The resource already exists or was changed concurrently
+    Conflict,
+    /// The upstream service could not be reached or failed to respond
+    UpstreamUnavailable,
+    /// The request was rejected as malformed
+    BadInput,
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::Unauthenticated => "UNAUTHENTICATED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::UpstreamUnavailable => "UPSTREAM_UNAVAILABLE",
+            ErrorCode::BadInput => "BAD_INPUT",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// An error with a known [`ErrorCode`]
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{message}")]
+pub(super) struct CodedError {
+    /// The code reported to clients
+    code: ErrorCode,
+    /// A human readable description of the error
+    message: String,
+}
+
+impl CodedError {
+    /// Create an error with the given code
+    pub(super) fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Create a [`ErrorCode::NotFound`] error
+    pub(super) fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    /// Create a [`ErrorCode::BadInput`] error
+    pub(super) fn bad_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::BadInput, message)
+    }
+
+    /// Create a [`ErrorCode::UpstreamUnavailable`] error
+    pub(super) fn upstream_unavailable(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::UpstreamUnavailable, message)
+    }
+
+    /// Create an error from the HTTP status of an unsuccessful response
+    pub(super) fn from_status(status: StatusCode, message: impl Into<String>) -> Self {
+        Self::new(status_error_code(status), message)
+    }
+
+    /// Create an error from the gRPC code reported by the Argo Server, if any
+    pub(super) fn from_grpc_code(code: Option<i32>, message: impl Into<String>) -> Self {
+        Self::new(
+            code.map_or(ErrorCode::UpstreamUnavailable, grpc_error_code),
+            message,
+        )
+    }
+
+    /// Create an error from an unsuccessful response of the Argo Server, preferring the gRPC
+    /// code in its body over the HTTP status
+    pub(super) async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        match response.json::<GrpcGatewayRuntimeError>().await {
+            Ok(err) => Self::new(
+                err.code
+                    .map_or_else(|| status_error_code(status), grpc_error_code),
+                err.to_string(),
+            ),
+            Err(_) => Self::new(status_error_code(status), status.to_string()),
+        }
+    }
+}
+
+/// The [`ErrorCode`] of a gRPC status code
+fn grpc_error_code(code: i32) -> ErrorCode {
+    match code {
+        // INVALID_ARGUMENT, FAILED_PRECONDITION, OUT_OF_RANGE
+        3 | 9 | 11 => ErrorCode::BadInput,
+        // NOT_FOUND
+        5 => ErrorCode::NotFound,
+        // ALREADY_EXISTS, ABORTED
+        6 | 10 => ErrorCode::Conflict,
+        // PERMISSION_DENIED
+        7 => ErrorCode::Forbidden,
+        // UNAUTHENTICATED
+        16 => ErrorCode::Unauthenticated,
+        _ => ErrorCode::UpstreamUnavailable,
+    }
+}
+
+/// The [`ErrorCode`] of an unsuccessful HTTP status
+fn status_error_code(status: StatusCode) -> ErrorCode {
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => ErrorCode::BadInput,
+        StatusCode::UNAUTHORIZED => ErrorCode::Unauthenticated,
+        StatusCode::FORBIDDEN => ErrorCode::Forbidden,
+        StatusCode::NOT_FOUND => ErrorCode::NotFound,
+        StatusCode::CONFLICT => ErrorCode::Conflict,
+        _ => ErrorCode::UpstreamUnavailable,
+    }
+}
+
+/// The code of the first error in the chain which describes an upstream failure
+fn error_code(err: &anyhow::Error) -> Option<ErrorCode> {
+    err.chain().find_map(|cause| {
+        if let Some(err) = cause.downcast_ref::<CodedError>() {
+            Some(err.code)
+        } else if let Some(err) = cause.downcast_ref::<GrpcGatewayRuntimeError>() {
+            Some(
+                err.code
+                    .map_or(ErrorCode::UpstreamUnavailable, grpc_error_code),
+            )
+        } else if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+            Some(
+                err.status()
+                    .map_or(ErrorCode::UpstreamUnavailable, status_error_code),
+            )
+        } else if let Some(err) = cause.downcast_ref::<kube::Error>() {
+            Some(match err {
+                kube::Error::Api(response) => StatusCode::from_u16(response.code)
+                    .map_or(ErrorCode::UpstreamUnavailable, status_error_code),
+                _ => ErrorCode::UpstreamUnavailable,
+            })
+        } else if let Some(err) = cause.downcast_ref::<TriggerError>() {
+            match err {
+                TriggerError::ForbiddenAccess => Some(ErrorCode::Forbidden),
+                TriggerError::ConfigInferError | TriggerError::ClientCreationError => {
+                    Some(ErrorCode::UpstreamUnavailable)
+                }
+                _ => None,
+            }
+        } else {
+            None
+        }
+    })
+}
+
+/// The HTTP status with which REST endpoints report an upstream failure, treating failures of
+/// unknown cause as the upstream service being unavailable
+pub(super) fn error_status(err: &anyhow::Error) -> StatusCode {
+    match error_code(err) {
+        Some(ErrorCode::NotFound) => StatusCode::NOT_FOUND,
+        Some(ErrorCode::Unauthenticated) => StatusCode::UNAUTHORIZED,
+        Some(ErrorCode::Forbidden) => StatusCode::FORBIDDEN,
+        Some(ErrorCode::Conflict) => StatusCode::CONFLICT,
+        Some(ErrorCode::BadInput) => StatusCode::BAD_REQUEST,
+        Some(ErrorCode::UpstreamUnavailable) | None => StatusCode::BAD_GATEWAY,
+    }
+}
+
+/// Sets the `code` extension of an error caused by an upstream failure, unless already set
+fn set_error_code(error: &mut ServerError) {
+    if error
+        .extensions
+        .as_ref()
+        .is_some_and(|extensions| extensions.get("code").is_some())
+    {
+        return;
+    }
+    let code = match error.source::<CodedError>() {
+        Some(err) => Some(err.code),
+        None => error.source::<anyhow::Error>().and_then(error_code),
+    };
+    if let Some(code) = code {
+        error
+            .extensions
+            .get_or_insert_with(Default::default)
+            .set("code", code.to_string());
+    }
+}
+
+/// Reports the cause of upstream failures in the `code` extension of GraphQL errors, so
+/// clients can tell missing, forbidden and unavailable resources apart
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UpstreamErrorCodes;
+
+impl ExtensionFactory for UpstreamErrorCodes {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(*self)
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for UpstreamErrorCodes {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let mut response = next.run(ctx, operation_name).await;
+        response.errors.iter_mut().for_each(set_error_code);
+        response
+    }
+
+    fn subscribe<'s>(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        stream: BoxStream<'s, Response>,
+        next: NextSubscribe<'_>,
+    ) -> BoxStream<'s, Response> {
+        next.run(ctx, stream)
+            .map(|mut response| {
+                response.errors.iter_mut().for_each(set_error_code);
+                response
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{graphql::root_schema_builder, validate_token::ValidatedAuthToken, ArgoServerUrl};
+    use async_graphql::Value;
+    use axum_extra::headers::Authorization;
+    use rstest::rstest;
+    use url::Url;
+
+    const WORKFLOWS_QUERY: &str = r#"
+        query {
+            workflows(visit: {proposalCode: "mg", proposalNumber: 36964, number: 1}) {
+                nodes {
+                    name
+                }
+            }
+        }
+    "#;
+
+    fn test_token() -> ValidatedAuthToken {
+        let token = Authorization::bearer("test-token").expect("token always valid");
+        ValidatedAuthToken::Valid(token)
+    }
+
+    async fn workflows_error_code(argo_server_url: Url) -> Option<Value> {
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let response = schema.execute(WORKFLOWS_QUERY).await;
+        assert_eq!(response.errors.len(), 1);
+        response.errors[0]
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get("code"))
+            .cloned()
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(403, r#"{"code":7,"message":"namespace is forbidden"}"#, "FORBIDDEN")]
+    #[case(404, r#"{"code":5,"message":"namespace not found"}"#, "NOT_FOUND")]
+    #[case(400, r#"{"code":3,"message":"invalid label selector"}"#, "BAD_INPUT")]
+    #[case(401, r#"{"code":16,"message":"Unauthorized"}"#, "UNAUTHENTICATED")]
+    #[case(409, r#"{"code":6,"message":"workflow already exists"}"#, "CONFLICT")]
+    #[case(503, "upstream connect error", "UPSTREAM_UNAVAILABLE")]
+    #[case(200, r#"{"metadata":{},"items":"unexpected"}"#, "UPSTREAM_UNAVAILABLE")]
+    async fn upstream_errors_are_coded(
+        #[case] status: usize,
+        #[case] body: &str,
+        #[case] expected_code: &str,
+    ) {
+        let mut server = mockito::Server::new_async().await;
+        let workflows_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1")
+            .match_query(mockito::Matcher::Any)
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let code = workflows_error_code(Url::parse(&server.url()).unwrap()).await;
+
+        workflows_endpoint.assert_async().await;
+        assert_eq!(code, Some(Value::from(expected_code)));
+    }
+
+    #[tokio::test]
+    async fn unreachable_upstream_is_unavailable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let argo_server_url = Url::parse(&format!("http://{}", listener.local_addr().unwrap()));
+        drop(listener);
+
+        let code = workflows_error_code(argo_server_url.unwrap()).await;
+
+        assert_eq!(code, Some(Value::from("UPSTREAM_UNAVAILABLE")));
+    }
+
+    #[tokio::test]
+    async fn null_items_are_no_workflows() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/v1/workflows/mg36964-1")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"metadata":{},"items":null}"#)
+            .create_async()
+            .await;
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(test_token())
+            .finish();
+
+        let response = schema.execute(WORKFLOWS_QUERY).await;
+
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({"workflows": {"nodes": []}})
+        );
+    }
+}
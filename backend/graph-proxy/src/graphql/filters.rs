@@ -1,8 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
+use argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow;
 use async_graphql::{
     Enum, InputObject, InputValueError, InputValueResult, Scalar, ScalarType, Value,
 };
+use chrono::{DateTime, SecondsFormat, Utc};
 use url::Url;
 
 /// Build labels to apply query to workflows API
@@ -39,6 +41,17 @@ impl WorkflowTemplatesFilter {
             .append_pair("listOptions.labelSelector", labels);
     }
 
+    /// Checks whether a template with the given labels satisfies every filter
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        self.create_label_selection()
+            .split(',')
+            .filter(|selector| !selector.is_empty())
+            .all(|selector| match selector.split_once('=') {
+                Some((key, value)) => labels.get(key).is_some_and(|label| label == value),
+                None => labels.contains_key(selector),
+            })
+    }
+
     /// Creates string of requested labels
     fn create_label_selection(&self) -> String {
         let mut label_selectors = Vec::new();
@@ -137,6 +150,16 @@ pub struct WorkflowFilter {
     /// Additional label selectors for filtering workflows
     #[graphql(name = "labelSelectors")]
     labels: Option<Vec<LabelSelector>>,
+    /// Only include workflows created after this time
+    created_after: Option<DateTime<Utc>>,
+    /// Only include workflows created before this time
+    created_before: Option<DateTime<Utc>>,
+    /// Only include workflows which finished after this time
+    finished_after: Option<DateTime<Utc>>,
+    /// Only include workflows whose name starts with this prefix
+    name_prefix: Option<String>,
+    /// The order in which workflows are returned
+    sort: Option<WorkflowSortOrder>,
 }
 
 impl WorkflowFilter {
@@ -147,6 +170,88 @@ impl WorkflowFilter {
             .append_pair("listOptions.labelSelector", labels);
     }
 
+    /// Generates and applies all the filters, restricted to workflows created by the given user
+    pub fn generate_creator_filters(&self, url: &mut Url, creator_id: &CreatorId) {
+        let mut label_selectors = Vec::new();
+        creator_id.generate_labels(&mut label_selectors);
+        let labels = self.create_label_selection();
+        if !labels.is_empty() {
+            label_selectors.push(labels);
+        }
+        url.query_pairs_mut()
+            .append_pair("listOptions.labelSelector", &label_selectors.join(","));
+    }
+
+    /// Applies the filters supported only by the workflow archive
+    ///
+    /// The archive can only select by start time, so only workflows started after the
+    /// `createdAfter` bound are selected, which includes every workflow created after it. The
+    /// remaining bounds are applied by the proxy.
+    pub fn generate_archive_filters(&self, url: &mut Url) {
+        if let Some(name_prefix) = &self.name_prefix {
+            url.query_pairs_mut().append_pair("namePrefix", name_prefix);
+        }
+        if let Some(created_after) = self.created_after {
+            url.query_pairs_mut().append_pair(
+                "listOptions.fieldSelector",
+                &format!(
+                    "spec.startedAt>{}",
+                    created_after.to_rfc3339_opts(SecondsFormat::Secs, true)
+                ),
+            );
+        }
+    }
+
+    /// Whether any of the filters must be applied by the proxy, as the Workflow API cannot
+    pub fn requires_proxy_filtering(&self) -> bool {
+        self.created_after.is_some()
+            || self.created_before.is_some()
+            || self.finished_after.is_some()
+            || self.name_prefix.is_some()
+            || self.sort_order() != WorkflowSortOrder::NewestFirst
+    }
+
+    /// Checks a workflow against the filters which must be applied by the proxy
+    pub fn matches(&self, workflow: &IoArgoprojWorkflowV1alpha1Workflow) -> bool {
+        let created = workflow
+            .metadata
+            .creation_timestamp
+            .as_ref()
+            .map(|time| **time);
+        let finished = workflow
+            .status
+            .as_ref()
+            .and_then(|status| status.finished_at.as_ref())
+            .map(|time| **time);
+
+        self.created_after
+            .is_none_or(|bound| created.is_some_and(|created| created > bound))
+            && self
+                .created_before
+                .is_none_or(|bound| created.is_some_and(|created| created < bound))
+            && self
+                .finished_after
+                .is_none_or(|bound| finished.is_some_and(|finished| finished > bound))
+            && self.name_prefix.as_ref().is_none_or(|prefix| {
+                workflow
+                    .metadata
+                    .name
+                    .as_ref()
+                    .is_some_and(|name| name.starts_with(prefix))
+            })
+    }
+
+    /// The requested order of the workflows
+    pub fn sort_order(&self) -> WorkflowSortOrder {
+        self.sort.unwrap_or_default()
+    }
+
+    /// Sorts workflows in the given order, if one is given, rather than that of the filter
+    pub fn with_sort_order(mut self, sort: Option<WorkflowSortOrder>) -> Self {
+        self.sort = sort.or(self.sort);
+        self
+    }
+
     /// Creates a string of all the reqested filters that belong to the
     /// `labelSelectors` query key in the Workflow API
     fn create_label_selection(&self) -> String {
@@ -247,6 +352,32 @@ impl GraphFilter for Creator {
     }
 }
 
+/// The unique ID (OIDC subject) of the user who created the workflow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatorId(pub String);
+
+impl GraphFilter for CreatorId {
+    fn generate_labels(&self, labels: &mut Vec<String>) {
+        let label = format!("workflows.argoproj.io/creator={}", self.0);
+        labels.push(label);
+    }
+}
+
+/// The order in which workflows are returned
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[allow(clippy::enum_variant_names)]
+pub enum WorkflowSortOrder {
+    /// Most recently created workflows first
+    #[default]
+    NewestFirst,
+    /// Least recently created workflows first
+    OldestFirst,
+    /// Workflows which ran for the longest first
+    LongestFirst,
+    /// Workflows which ran for the shortest first
+    ShortestFirst,
+}
+
 /// The workflow template
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Template(String);
@@ -331,9 +462,12 @@ impl LabelSelector {
 #[cfg(test)]
 mod tests {
     use crate::graphql::filters::{
-        Creator, LabelSelector, ScienceGroup, Template, WorkflowFilter,
-        WorkflowLabelSelectorOperator, WorkflowStatusFilter, WorkflowTemplatesFilter,
+        Creator, CreatorId, LabelSelector, ScienceGroup, Template, WorkflowFilter,
+        WorkflowLabelSelectorOperator, WorkflowSortOrder, WorkflowStatusFilter,
+        WorkflowTemplatesFilter,
     };
+    use argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow;
+    use serde_json::json;
     // TEMPLATES--------------------------------------------
     #[tokio::test]
     async fn science_group_filter() {
@@ -397,6 +531,7 @@ mod tests {
                 operator: WorkflowLabelSelectorOperator::Eq,
                 values: Some(vec!["i14".to_string()]),
             }]),
+            ..Default::default()
         };
 
         assert_eq!(filters.create_label_selection(), "beamline=i14");
@@ -415,6 +550,7 @@ mod tests {
                 operator: WorkflowLabelSelectorOperator::Eq,
                 values: Some(vec!["i14".to_string()]),
             }]),
+            ..Default::default()
         };
 
         assert_eq!(
@@ -434,6 +570,7 @@ mod tests {
                 operator: WorkflowLabelSelectorOperator::Eq,
                 values: Some(vec!["i14".to_string()]),
             }]),
+            ..Default::default()
         };
 
         assert_eq!(filters.create_label_selection(), "beamline=i14");
@@ -448,6 +585,7 @@ mod tests {
             template: None,
             workflow_status_filter: None,
             labels: None,
+            ..Default::default()
         };
 
         let labels = filters.create_label_selection();
@@ -472,6 +610,7 @@ mod tests {
             template: None,
             workflow_status_filter: Some(phases),
             labels: None,
+            ..Default::default()
         };
 
         let labels = filters.create_label_selection();
@@ -493,6 +632,7 @@ mod tests {
             template: None,
             workflow_status_filter: Some(phases),
             labels: None,
+            ..Default::default()
         };
 
         let labels = filters.create_label_selection();
@@ -516,9 +656,128 @@ mod tests {
             template: Some(template),
             workflow_status_filter: Some(phases),
             labels: None,
+            ..Default::default()
         };
 
         let labels = filters.create_label_selection();
         assert_eq!(labels, "workflows.argoproj.io/phase in (Running),workflows.argoproj.io/creator-preferred-username=test,workflows.argoproj.io/cluster-workflow-template=template-name");
     }
+
+    #[tokio::test]
+    async fn creator_id_template() {
+        let template = Template("template-name".to_string());
+
+        let filters = WorkflowFilter {
+            creator: None,
+            template: Some(template),
+            workflow_status_filter: None,
+            labels: None,
+            ..Default::default()
+        };
+
+        let mut url = url::Url::parse("http://argo/api/v1/workflows/mg36964-1").unwrap();
+        filters.generate_creator_filters(&mut url, &CreatorId("1234-abcd".to_string()));
+        let (_, labels) = url.query_pairs().next().unwrap();
+        assert_eq!(labels, "workflows.argoproj.io/creator=1234-abcd,workflows.argoproj.io/cluster-workflow-template=template-name");
+    }
+
+    fn workflow(
+        name: &str,
+        created: &str,
+        finished: Option<&str>,
+    ) -> IoArgoprojWorkflowV1alpha1Workflow {
+        serde_json::from_value(json!({
+            "metadata": {
+                "name": name,
+                "creationTimestamp": created,
+            },
+            "spec": {},
+            "status": {
+                "phase": if finished.is_some() { "Failed" } else { "Running" },
+                "startedAt": created,
+                "finishedAt": finished,
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn created_time_range() {
+        let filters = WorkflowFilter {
+            created_after: Some("2025-03-01T18:00:00Z".parse().unwrap()),
+            created_before: Some("2025-03-02T06:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let mut url = url::Url::parse("http://argo/api/v1/archived-workflows").unwrap();
+        filters.generate_archive_filters(&mut url);
+        assert_eq!(
+            url.query_pairs().collect::<Vec<_>>(),
+            vec![(
+                "listOptions.fieldSelector".into(),
+                "spec.startedAt>2025-03-01T18:00:00Z".into()
+            )]
+        );
+        assert!(filters.requires_proxy_filtering());
+        assert!(filters.matches(&workflow("night-shift", "2025-03-01T23:10:00Z", None)));
+        assert!(!filters.matches(&workflow("day-shift", "2025-03-01T12:00:00Z", None)));
+        assert!(!filters.matches(&workflow("next-day", "2025-03-02T09:00:00Z", None)));
+    }
+
+    #[tokio::test]
+    async fn finished_after() {
+        let filters = WorkflowFilter {
+            finished_after: Some("2025-03-01T18:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+
+        assert!(filters.matches(&workflow(
+            "late-finish",
+            "2025-03-01T17:00:00Z",
+            Some("2025-03-01T19:00:00Z")
+        )));
+        assert!(!filters.matches(&workflow(
+            "early-finish",
+            "2025-03-01T17:00:00Z",
+            Some("2025-03-01T17:30:00Z")
+        )));
+        assert!(!filters.matches(&workflow("unfinished", "2025-03-01T19:00:00Z", None)));
+    }
+
+    #[tokio::test]
+    async fn name_prefix() {
+        let filters = WorkflowFilter {
+            name_prefix: Some("numpy-".to_string()),
+            ..Default::default()
+        };
+
+        let mut url = url::Url::parse("http://argo/api/v1/archived-workflows").unwrap();
+        filters.generate_archive_filters(&mut url);
+        assert_eq!(url.query(), Some("namePrefix=numpy-"));
+        assert!(filters.matches(&workflow(
+            "numpy-benchmark-wdkwj",
+            "2025-03-01T17:00:00Z",
+            None
+        )));
+        assert!(!filters.matches(&workflow(
+            "conditional-steps-x2k9d",
+            "2025-03-01T17:00:00Z",
+            None
+        )));
+    }
+
+    #[tokio::test]
+    async fn sort_only_requires_proxy_filtering_when_not_default() {
+        let filters = WorkflowFilter {
+            sort: Some(WorkflowSortOrder::NewestFirst),
+            ..Default::default()
+        };
+        assert!(!filters.requires_proxy_filtering());
+
+        let filters = WorkflowFilter {
+            sort: Some(WorkflowSortOrder::LongestFirst),
+            ..Default::default()
+        };
+        assert!(filters.requires_proxy_filtering());
+    }
 }
@@ -0,0 +1,247 @@
+use super::{Mutation, Query, Subscription};
+use crate::validate_token::ValidatedAuthToken;
+use async_graphql::{ErrorExtensions, Pos, Response, SchemaBuilder};
+use clap::Parser;
+use jsonwebtoken::dangerous::insecure_decode;
+use moka::future::Cache;
+use serde::Deserialize;
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Arguments for limiting the cost of GraphQL requests
+#[derive(Debug, Parser, Clone, Default)]
+pub struct QueryLimitArgs {
+    /// The maximum depth of a query, unlimited if not set
+    #[arg(long, env)]
+    pub query_depth_limit: Option<usize>,
+    /// The maximum complexity of a query, unlimited if not set
+    #[arg(long, env)]
+    pub query_complexity_limit: Option<usize>,
+    /// The sustained number of requests per second allowed for each subject, unlimited if not set
+    #[arg(long, env)]
+    pub rate_limit_per_second: Option<f64>,
+    /// The number of requests each subject may make in a burst above the sustained rate
+    #[arg(long, env, default_value_t = 50)]
+    pub rate_limit_burst: u32,
+}
+
+impl QueryLimitArgs {
+    /// Applies the depth and complexity limits to a schema
+    pub fn apply(
+        &self,
+        mut builder: SchemaBuilder<Query, Mutation, Subscription>,
+    ) -> SchemaBuilder<Query, Mutation, Subscription> {
+        if let Some(depth) = self.query_depth_limit {
+            builder = builder.limit_depth(depth);
+        }
+        if let Some(complexity) = self.query_complexity_limit {
+            builder = builder.limit_complexity(complexity);
+        }
+        builder
+    }
+
+    /// Creates a [`RateLimiter`], if a rate limit is configured
+    pub fn rate_limiter(&self) -> Option<RateLimiter> {
+        self.rate_limit_per_second
+            .map(|per_second| RateLimiter::new(per_second, self.rate_limit_burst))
+    }
+}
+
+/// Error codes returned when a request is refused due to its cost
+pub enum LimitErrorCode {
+    /// The subject has exceeded their request rate
+    RateLimited,
+}
+
+impl Display for LimitErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            LimitErrorCode::RateLimited => "RATE_LIMITED",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// A token bucket, refilled continuously up to its capacity
+#[derive(Debug)]
+struct TokenBucket {
+    /// The number of requests which may currently be made
+    tokens: f64,
+    /// The time at which the bucket was last refilled
+    refilled_at: Instant,
+}
+
+/// The issuer and subject identifying the holder of a token
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+struct TokenSubject {
+    /// The issuer of the token, as subjects are only unique within an issuer
+    iss: Option<String>,
+    /// The subject of the token
+    sub: String,
+}
+
+/// Limits the rate of requests made by each subject, using a token bucket per subject
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    /// The token bucket of each subject
+    buckets: Cache<TokenSubject, Arc<Mutex<TokenBucket>>>,
+    /// The number of tokens added to each bucket per second
+    per_second: f64,
+    /// The maximum number of tokens held by each bucket
+    burst: f64,
+}
+
+impl RateLimiter {
+    /// Creates a limiter which allows a sustained rate of `per_second` requests, with bursts of up to `burst`
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            buckets: Cache::builder()
+                .max_capacity(100_000)
+                .time_to_idle(
+                    Duration::try_from_secs_f64(burst / per_second)
+                        .unwrap_or(Duration::MAX)
+                        .clamp(Duration::from_secs(60), Duration::from_secs(86_400)),
+                )
+                .build(),
+            per_second,
+            burst,
+        }
+    }
+
+    /// Takes a token from the bucket of the token's subject, returning false if none remain.
+    ///
+    /// Requests without a valid token are not limited, as they are rejected before reaching upstream services.
+    pub async fn check(&self, auth_token: &ValidatedAuthToken) -> bool {
+        let Some(subject) = token_subject(auth_token) else {
+            return true;
+        };
+        let burst = self.burst;
+        let bucket = self
+            .buckets
+            .get_with(subject, async move {
+                Arc::new(Mutex::new(TokenBucket {
+                    tokens: burst,
+                    refilled_at: Instant::now(),
+                }))
+            })
+            .await;
+        let mut bucket = bucket.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.per_second).min(self.burst);
+        bucket.refilled_at = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+/// The issuer and subject of a validated token, or the token itself if it has no subject claim
+fn token_subject(auth_token: &ValidatedAuthToken) -> Option<TokenSubject> {
+    let token = auth_token.as_token()?.token();
+    Some(
+        insecure_decode::<TokenSubject>(token)
+            .map(|data| data.claims)
+            .unwrap_or_else(|_| TokenSubject {
+                iss: None,
+                sub: token.to_string(),
+            }),
+    )
+}
+
+/// The error returned for a request refused by the [`RateLimiter`]
+pub fn rate_limited_error() -> async_graphql::Error {
+    async_graphql::Error::new("Rate limit exceeded, please retry later")
+        .extend_with(|_, e| e.set("code", LimitErrorCode::RateLimited.to_string()))
+}
+
+/// The response to a request refused by the [`RateLimiter`]
+pub fn rate_limited_response() -> Response {
+    Response::from_errors(vec![rate_limited_error().into_server_error(Pos::default())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryLimitArgs, RateLimiter};
+    use crate::{graphql::root_schema_builder, validate_token::ValidatedAuthToken};
+    use axum_extra::headers::Authorization;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::json;
+
+    fn token_for(subject: &str) -> ValidatedAuthToken {
+        token_from("https://authn.diamond.ac.uk/realms/master", subject)
+    }
+
+    fn token_from(issuer: &str, subject: &str) -> ValidatedAuthToken {
+        let token = encode(
+            &Header::default(),
+            &json!({"iss": issuer, "sub": subject}),
+            &EncodingKey::from_secret(b"test-secret"),
+        )
+        .unwrap();
+        ValidatedAuthToken::Valid(Authorization::bearer(&token).unwrap())
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_limits_each_subject() {
+        let rate_limiter = RateLimiter::new(0.001, 2);
+        let first = token_for("first-user");
+        let second = token_for("second-user");
+
+        assert!(rate_limiter.check(&first).await);
+        assert!(rate_limiter.check(&first).await);
+        assert!(!rate_limiter.check(&first).await);
+        assert!(rate_limiter.check(&second).await);
+        assert!(rate_limiter.check(&ValidatedAuthToken::Missing).await);
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_separates_subjects_of_each_issuer() {
+        let rate_limiter = RateLimiter::new(0.001, 1);
+        let primary = token_from("https://authn.diamond.ac.uk/realms/master", "user");
+        let staging = token_from("https://authn.diamond.ac.uk/realms/staging", "user");
+
+        assert!(rate_limiter.check(&primary).await);
+        assert!(!rate_limiter.check(&primary).await);
+        assert!(rate_limiter.check(&staging).await);
+    }
+
+    #[tokio::test]
+    async fn complex_artifact_query_is_rejected() {
+        let limits = QueryLimitArgs {
+            query_complexity_limit: Some(500),
+            ..Default::default()
+        };
+        let schema = limits
+            .apply(root_schema_builder())
+            .data(token_for("user"))
+            .finish();
+        let query = r#"
+            query {
+                workflows(visit: {proposalCode: "mg", proposalNumber: 36964, number: 1}, limit: 30) {
+                    nodes {
+                        status {
+                            ...on WorkflowSucceededStatus {
+                                tasks {
+                                    artifacts {
+                                        url
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+        let response = schema.execute(query).await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].message, "Query is too complex.");
+    }
+}
@@ -0,0 +1,161 @@
+use super::workflows::fetch_workflow_details;
+use crate::{
+    metrics::MetricsState,
+    s3client::{ArtifactMetadata, ArtifactMetadataCache},
+    validate_token::ValidatedAuthToken,
+    ArgoServerUrl, S3Bucket,
+};
+use argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow;
+use async_graphql::dataloader::{DataLoader, HashMapCache, Loader};
+use aws_sdk_s3::Client;
+use axum_extra::headers::{authorization::Bearer, Authorization};
+use futures_util::future::join_all;
+use opentelemetry::KeyValue;
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
+
+/// The result of loading a single key, kept separate so one failure does not fail the whole batch
+type LoadResult<T> = Result<Arc<T>, Arc<anyhow::Error>>;
+
+/// A per-request [`DataLoader`] of workflow details
+pub(super) type WorkflowDataLoader = DataLoader<WorkflowLoader, HashMapCache>;
+
+/// A per-request [`DataLoader`] of artifact metadata
+pub(super) type ArtifactMetadataDataLoader = DataLoader<ArtifactMetadataLoader, HashMapCache>;
+
+/// The upstream clients used to build the [`DataLoader`]s of each request
+#[derive(Debug, Clone)]
+pub struct LoaderState {
+    /// The URL of the Argo Server
+    pub argo_server_url: ArgoServerUrl,
+    /// The client used to query the artifact store
+    pub s3_client: Client,
+    /// The bucket in which artifacts are stored
+    pub s3_bucket: S3Bucket,
+    /// The cache of artifact metadata shared between requests
+    pub artifact_metadata_cache: ArtifactMetadataCache,
+    /// Metrics recording the size of each batch
+    pub metrics_state: MetricsState,
+}
+
+impl LoaderState {
+    /// Create a [`WorkflowDataLoader`] which fetches workflows on behalf of the token holder
+    pub(super) fn workflow_loader(&self, auth_token: &ValidatedAuthToken) -> WorkflowDataLoader {
+        DataLoader::with_cache(
+            WorkflowLoader {
+                argo_server_url: self.argo_server_url.clone(),
+                auth_token: auth_token.as_token().cloned(),
+                metrics_state: self.metrics_state.clone(),
+            },
+            tokio::spawn,
+            HashMapCache::default(),
+        )
+    }
+
+    /// Create an [`ArtifactMetadataDataLoader`]
+    pub(super) fn artifact_metadata_loader(&self) -> ArtifactMetadataDataLoader {
+        DataLoader::with_cache(
+            ArtifactMetadataLoader {
+                s3_client: self.s3_client.clone(),
+                s3_bucket: self.s3_bucket.clone(),
+                cache: self.artifact_metadata_cache.clone(),
+                metrics_state: self.metrics_state.clone(),
+            },
+            tokio::spawn,
+            HashMapCache::default(),
+        )
+    }
+}
+
+/// Identifies a workflow in either the cluster or the workflow archive
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct WorkflowKey {
+    /// The visit the workflow was run against
+    pub(super) visit: String,
+    /// The name of the workflow
+    pub(super) name: String,
+    /// The unique ID of the workflow
+    pub(super) uid: String,
+    /// Whether the workflow should be retrieved from the workflow archive
+    pub(super) archived: bool,
+}
+
+/// Loads full workflow manifests from the Argo Server
+pub(super) struct WorkflowLoader {
+    /// The URL of the Argo Server
+    argo_server_url: ArgoServerUrl,
+    /// The token used to authorize requests to the Argo Server
+    auth_token: Option<Authorization<Bearer>>,
+    /// Metrics recording the size of each batch
+    metrics_state: MetricsState,
+}
+
+impl Loader<WorkflowKey> for WorkflowLoader {
+    type Value = LoadResult<IoArgoprojWorkflowV1alpha1Workflow>;
+    type Error = Infallible;
+
+    async fn load(
+        &self,
+        keys: &[WorkflowKey],
+    ) -> Result<HashMap<WorkflowKey, Self::Value>, Self::Error> {
+        self.metrics_state
+            .dataloader_batch_size
+            .record(keys.len() as u64, &[KeyValue::new("loader", "workflow")]);
+        Ok(join_all(keys.iter().map(|key| async move {
+            let workflow =
+                fetch_workflow_details(&self.argo_server_url, self.auth_token.as_ref(), key)
+                    .await
+                    .map(Arc::new)
+                    .map_err(Arc::new);
+            (key.clone(), workflow)
+        }))
+        .await
+        .into_iter()
+        .collect())
+    }
+}
+
+/// Loads the metadata of artifacts, keyed by object key, through the shared [`ArtifactMetadataCache`]
+pub(super) struct ArtifactMetadataLoader {
+    /// The client used to query the artifact store
+    s3_client: Client,
+    /// The bucket in which artifacts are stored
+    s3_bucket: S3Bucket,
+    /// The cache of artifact metadata shared between requests
+    cache: ArtifactMetadataCache,
+    /// Metrics recording the size of each batch
+    metrics_state: MetricsState,
+}
+
+impl Loader<String> for ArtifactMetadataLoader {
+    type Value = LoadResult<ArtifactMetadata>;
+    type Error = Infallible;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        self.metrics_state.dataloader_batch_size.record(
+            keys.len() as u64,
+            &[KeyValue::new("loader", "artifact_metadata")],
+        );
+        Ok(join_all(keys.iter().map(|key| async move {
+            let metadata = self
+                .cache
+                .get(&self.s3_client, &self.s3_bucket, key)
+                .await
+                .map_err(Arc::new);
+            (key.clone(), metadata)
+        }))
+        .await
+        .into_iter()
+        .collect())
+    }
+}
+
+/// Convert the result of a [`DataLoader`] lookup back into an [`anyhow::Result`]
+pub(super) fn loaded<T>(
+    result: Result<Option<LoadResult<T>>, Infallible>,
+) -> anyhow::Result<Arc<T>> {
+    match result {
+        Ok(Some(value)) => value.map_err(|err| anyhow::anyhow!("{err}")),
+        Ok(None) => Err(anyhow::anyhow!("Value was not loaded")),
+        Err(never) => match never {},
+    }
+}
@@ -1,28 +1,48 @@
+/// Authenticated streaming of artifacts through the proxy
+mod artifacts;
+/// Mapping of upstream failures to GraphQL error codes
+mod errors;
 /// Workflow/Template filters
 mod filters;
+/// Limits on the depth, complexity and rate of requests
+mod limits;
+/// Per-request batching and deduplication of upstream calls
+mod loaders;
 /// Workflow Template Paramer Schema
 mod parameter_schema;
+/// Automatic persisted queries and the operation allowlist
+mod persisted_queries;
+/// Kubernetes Events and status of the pods running tasks
+mod pods;
+/// Role-based authorization from the groups or roles in token claims
+mod roles;
 /// GraphQL operations requiring subscriptions
 mod subscription;
 /// Axum-specific websocket handling to support subscriptions
 pub mod subscription_integration;
+/// Watch-backed cache of workflow templates
+mod template_cache;
 /// GraphQL operations related to Triggers
 mod triggers;
 /// Workflow Template JSON Forms UI Schema
 mod ui_schema;
+/// Watch-backed cache of the visits of each user and instrument
+mod visit_cache;
 /// GraphQL operations related to workflow templates
 mod workflow_templates;
 /// GraphQL operations related to workflows
 mod workflows;
 
+use crate::graphql::auth_guard::AuthGuard;
 use crate::RouterState;
-use crate::{graphql::auth_guard::AuthGuard, validate_token::ValidationMethod};
 
 use self::{
+    artifacts::ArtifactsMutation,
+    errors::{CodedError, UpstreamErrorCodes},
     subscription::WorkflowsSubscription,
     triggers::{TriggerMutation, TriggerQuery},
     workflow_templates::WorkflowTemplatesQuery,
-    workflows::{Workflow, WorkflowsQuery},
+    workflows::{Workflow, WorkflowsMutation, WorkflowsQuery},
 };
 use async_graphql::{
     extensions::Analyzer, parser::parse_query, Context, InputObject, MergedObject,
@@ -39,6 +59,14 @@ use opentelemetry::KeyValue;
 use std::fmt::Display;
 use std::str::FromStr;
 use workflow_templates::WorkflowTemplatesMutation;
+
+pub use artifacts::{artifact_bundle_handler, artifact_handler, ArtifactRouterState};
+pub use limits::{QueryLimitArgs, RateLimiter};
+pub use loaders::LoaderState;
+pub use persisted_queries::PersistedQueryArgs;
+pub use roles::RoleArgs;
+pub use template_cache::TemplateCache;
+pub use visit_cache::VisitCache;
 /// Ensure valid authn on GraphQL fields
 mod auth_guard;
 
@@ -54,6 +82,7 @@ pub fn root_schema_builder() -> SchemaBuilder<Query, Mutation, Subscription> {
     )
     .enable_federation()
     .extension(Analyzer)
+    .extension(UpstreamErrorCodes)
 }
 
 /// The root query of the service
@@ -82,7 +111,12 @@ impl NodeQuery {
 
 /// The root mutation of the service
 #[derive(Debug, Clone, Default, MergedObject)]
-pub struct Mutation(WorkflowTemplatesMutation, TriggerMutation);
+pub struct Mutation(
+    ArtifactsMutation,
+    WorkflowsMutation,
+    WorkflowTemplatesMutation,
+    TriggerMutation,
+);
 
 /// Represents Relay Node types
 #[derive(Union)]
@@ -135,6 +169,12 @@ pub async fn graphql_handler(
             };
         }
         request_type = if has_mutation { "mutation" } else { "query" };
+    } else if query.query.is_empty() && query.extensions.contains_key("persistedQuery") {
+        request_type = "persisted";
+        state
+            .metrics_state
+            .total_requests
+            .add(1, &[KeyValue::new("request_type", "persisted")]);
     } else {
         state
             .metrics_state
@@ -144,10 +184,24 @@ pub async fn graphql_handler(
 
     let auth_token = state
         .token_validator
-        .validate_token(auth_token_header.map(|it| it.0), ValidationMethod::Jwt)
+        .validate_token(auth_token_header.map(|it| it.0), state.validation_method)
         .await;
 
-    let mut response = state.schema.execute(query.data(auth_token)).await;
+    if let Some(rate_limiter) = &state.rate_limiter {
+        if !rate_limiter.check(&auth_token).await {
+            state
+                .metrics_state
+                .total_errors
+                .add(1, &[KeyValue::new("status", "rate_limited")]);
+            return limits::rate_limited_response().into();
+        }
+    }
+
+    let query = query
+        .data(state.loader_state.workflow_loader(&auth_token))
+        .data(state.loader_state.artifact_metadata_loader())
+        .data(auth_token);
+    let mut response = state.schema.execute(query).await;
     if let Some(Value::Object(analyzer)) = response.extensions.remove("analyzer") {
         let depth = analyzer.get("depth").and_then(|value| match value {
             Value::Number(number) => number.as_u64(),
@@ -253,15 +307,15 @@ impl FromStr for VisitInput {
     fn from_str(string: &str) -> Result<Self, Self::Err> {
         let caps = VISIT_REGEX
             .captures(string)
-            .ok_or_else(|| anyhow::anyhow!("Invalid visit format"))?;
+            .ok_or_else(|| CodedError::bad_input("Invalid visit format"))?;
         Ok(VisitInput {
             proposal_code: caps[1].to_string(),
             proposal_number: caps[2]
                 .parse()
-                .map_err(|err| anyhow::anyhow!("Invalid proposal number: {err}"))?,
+                .map_err(|err| CodedError::bad_input(format!("Invalid proposal number: {err}")))?,
             number: caps[3]
                 .parse()
-                .map_err(|err| anyhow::anyhow!("Invalid visit number: {err}"))?,
+                .map_err(|err| CodedError::bad_input(format!("Invalid visit number: {err}")))?,
         })
     }
 }
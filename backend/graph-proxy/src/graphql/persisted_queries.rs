@@ -0,0 +1,228 @@
+use super::{Mutation, Query, Subscription};
+use async_graphql::{
+    extensions::{
+        apollo_persisted_queries::{ApolloPersistedQueries, LruCacheStorage},
+        Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+    },
+    Request, SchemaBuilder, ServerError, ServerResult,
+};
+use clap::Parser;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, fmt::Display, path::PathBuf, sync::Arc};
+
+/// Arguments for configuring persisted queries and the operation allowlist
+#[derive(Debug, Parser, Clone, Default)]
+pub struct PersistedQueryArgs {
+    /// The number of automatic persisted queries to cache, disabled if zero
+    #[arg(long, env, default_value_t = 1000)]
+    pub persisted_query_cache_size: usize,
+    /// An Apollo persisted query manifest, listing the only operations which may be executed
+    #[arg(long, env)]
+    pub operation_allowlist: Option<PathBuf>,
+}
+
+impl PersistedQueryArgs {
+    /// Registers the persisted query and allowlist extensions with a schema
+    pub fn apply(
+        &self,
+        mut builder: SchemaBuilder<Query, Mutation, Subscription>,
+    ) -> anyhow::Result<SchemaBuilder<Query, Mutation, Subscription>> {
+        if let Some(path) = &self.operation_allowlist {
+            let manifest =
+                serde_json::from_str::<PersistedQueryManifest>(&std::fs::read_to_string(path)?)?;
+            builder = builder.extension(OperationAllowlist::from(manifest));
+        }
+        if self.persisted_query_cache_size > 0 {
+            builder = builder.extension(ApolloPersistedQueries::new(LruCacheStorage::new(
+                self.persisted_query_cache_size,
+            )));
+        }
+        Ok(builder)
+    }
+}
+
+/// Error codes returned when an operation is refused by the [`OperationAllowlist`]
+pub enum AllowlistErrorCode {
+    /// The operation is not in the allowlist
+    OperationNotAllowed,
+}
+
+impl Display for AllowlistErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            AllowlistErrorCode::OperationNotAllowed => "OPERATION_NOT_ALLOWED",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// An operation listed in a [`PersistedQueryManifest`]
+#[derive(Debug, Deserialize)]
+struct ManifestOperation {
+    /// The GraphQL document of the operation
+    body: String,
+}
+
+/// An Apollo persisted query manifest, as produced by `generate-persisted-query-manifest`
+#[derive(Debug, Deserialize)]
+struct PersistedQueryManifest {
+    /// The operations which may be executed
+    operations: Vec<ManifestOperation>,
+}
+
+/// The SHA-256 hash of a query, as used by automatic persisted queries
+fn query_hash(query: &str) -> String {
+    format!("{:x}", Sha256::digest(query.as_bytes()))
+}
+
+/// Only allows operations whose documents are listed in a [`PersistedQueryManifest`].
+///
+/// Listed operations may also be requested by hash alone, as with automatic persisted queries.
+#[derive(Debug, Clone)]
+struct OperationAllowlist(Arc<HashMap<String, String>>);
+
+impl From<PersistedQueryManifest> for OperationAllowlist {
+    fn from(manifest: PersistedQueryManifest) -> Self {
+        Self(Arc::new(
+            manifest
+                .operations
+                .into_iter()
+                .map(|operation| (query_hash(&operation.body), operation.body))
+                .collect(),
+        ))
+    }
+}
+
+impl ExtensionFactory for OperationAllowlist {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for OperationAllowlist {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        mut request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let persisted_hash = request
+            .extensions
+            .get("persistedQuery")
+            .and_then(|persisted_query| serde_json::to_value(persisted_query).ok())
+            .and_then(|persisted_query| persisted_query["sha256Hash"].as_str().map(str::to_owned));
+        let hash = match (request.query.is_empty(), persisted_hash) {
+            (true, Some(hash)) => hash,
+            _ => query_hash(&request.query),
+        };
+        match self.0.get(&hash) {
+            Some(query) => {
+                request.query = query.clone();
+                next.run(ctx, request).await
+            }
+            None => {
+                let mut error = ServerError::new("Operation is not in the allowlist", None);
+                error
+                    .extensions
+                    .get_or_insert_with(Default::default)
+                    .set("code", AllowlistErrorCode::OperationNotAllowed.to_string());
+                Err(error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{query_hash, OperationAllowlist, PersistedQueryArgs, PersistedQueryManifest};
+    use crate::graphql::root_schema_builder;
+    use async_graphql::{Request, Value};
+    use serde_json::json;
+
+    const ALLOWED_QUERY: &str = "query Allowed { __typename }";
+
+    fn allowlist() -> OperationAllowlist {
+        let manifest = serde_json::from_value::<PersistedQueryManifest>(json!({
+            "format": "apollo-persisted-query-manifest",
+            "version": 1,
+            "operations": [{
+                "id": query_hash(ALLOWED_QUERY),
+                "name": "Allowed",
+                "type": "query",
+                "body": ALLOWED_QUERY
+            }]
+        }))
+        .unwrap();
+        OperationAllowlist::from(manifest)
+    }
+
+    fn persisted_request(query: &str, hash: &str) -> Request {
+        let mut request = Request::new(query);
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            Value::from_json(json!({"version": 1, "sha256Hash": hash})).unwrap(),
+        );
+        request
+    }
+
+    #[tokio::test]
+    async fn automatic_persisted_query_is_registered_and_reused() {
+        let args = PersistedQueryArgs {
+            persisted_query_cache_size: 10,
+            ..Default::default()
+        };
+        let schema = args.apply(root_schema_builder()).unwrap().finish();
+        let query = "query { __typename }";
+        let hash = query_hash(query);
+
+        let unknown = schema.execute(persisted_request("", &hash)).await;
+        assert_eq!(unknown.errors.len(), 1);
+        assert_eq!(unknown.errors[0].message, "PersistedQueryNotFound");
+
+        let registered = schema.execute(persisted_request(query, &hash)).await;
+        assert!(registered.errors.is_empty());
+
+        let reused = schema.execute(persisted_request("", &hash)).await;
+        assert!(reused.errors.is_empty());
+        assert_eq!(
+            reused.data.into_json().unwrap(),
+            json!({"__typename": "Query"})
+        );
+    }
+
+    #[tokio::test]
+    async fn unlisted_operation_is_rejected() {
+        let schema = root_schema_builder().extension(allowlist()).finish();
+
+        let response = schema.execute("query Unlisted { __typename }").await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(
+            response.errors[0]
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .cloned(),
+            Some(Value::from("OPERATION_NOT_ALLOWED"))
+        );
+    }
+
+    #[tokio::test]
+    async fn listed_operation_is_executed_by_query_or_hash() {
+        let schema = root_schema_builder().extension(allowlist()).finish();
+
+        let by_query = schema.execute(ALLOWED_QUERY).await;
+        assert!(by_query.errors.is_empty());
+
+        let by_hash = schema
+            .execute(persisted_request("", &query_hash(ALLOWED_QUERY)))
+            .await;
+        assert!(by_hash.errors.is_empty());
+        assert_eq!(
+            by_hash.data.into_json().unwrap(),
+            json!({"__typename": "Query"})
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::core::v1::{ContainerStatus as KubeContainerStatus, Event, Pod};
+use kube::{api::ListParams, Api, Client};
+
+/// A Kubernetes Event recorded against the pod of a task, such as a scheduling failure or an image pull error
+#[derive(Debug, Clone, SimpleObject)]
+pub(super) struct PodEvent {
+    /// The type of the event, either Normal or Warning
+    #[graphql(name = "type")]
+    type_: Option<String>,
+    /// A short, machine understandable reason for the event, such as FailedScheduling
+    reason: Option<String>,
+    /// A human readable description of the event
+    message: Option<String>,
+    /// The number of times the event has occurred
+    count: i32,
+    /// The time at which the event was first recorded
+    first_timestamp: Option<DateTime<Utc>>,
+    /// The time at which the most recent occurrence of the event was recorded
+    last_timestamp: Option<DateTime<Utc>>,
+    /// The component which reported the event, such as the scheduler or kubelet
+    source: Option<String>,
+}
+
+impl From<Event> for PodEvent {
+    fn from(event: Event) -> Self {
+        let event_time = event.event_time.map(|time| time.0);
+        Self {
+            type_: event.type_,
+            reason: event.reason,
+            message: event.message,
+            count: event.count.unwrap_or(1),
+            first_timestamp: event.first_timestamp.map(|time| time.0).or(event_time),
+            last_timestamp: event.last_timestamp.map(|time| time.0).or(event_time),
+            source: event
+                .source
+                .and_then(|source| source.component)
+                .or(event.reporting_component),
+        }
+    }
+}
+
+/// A condition of the pod of a task, such as PodScheduled or Ready
+#[derive(Debug, Clone, SimpleObject)]
+pub(super) struct PodCondition {
+    /// The type of the condition
+    #[graphql(name = "type")]
+    type_: String,
+    /// Whether the condition holds, one of True, False or Unknown
+    status: String,
+    /// A short, machine understandable reason for the last transition of the condition
+    reason: Option<String>,
+    /// A human readable description of the last transition of the condition
+    message: Option<String>,
+    /// The time at which the condition last transitioned
+    last_transition_time: Option<DateTime<Utc>>,
+}
+
+/// The state of a container
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub(super) enum ContainerState {
+    /// The container is waiting to start
+    Waiting,
+    /// The container is running
+    Running,
+    /// The container has terminated
+    Terminated,
+}
+
+/// The status of a container in the pod of a task
+#[derive(Debug, Clone, SimpleObject)]
+pub(super) struct ContainerStatus {
+    /// The name of the container
+    name: String,
+    /// The current state of the container
+    state: Option<ContainerState>,
+    /// A short, machine understandable reason for the state, such as ImagePullBackOff or OOMKilled
+    reason: Option<String>,
+    /// A human readable description of the state
+    message: Option<String>,
+    /// The exit code of the container, if it has terminated
+    exit_code: Option<i32>,
+    /// The number of times the container has been restarted
+    restart_count: i32,
+}
+
+impl From<KubeContainerStatus> for ContainerStatus {
+    fn from(status: KubeContainerStatus) -> Self {
+        let state = status.state.unwrap_or_default();
+        let (state, reason, message, exit_code) = if let Some(terminated) = state.terminated {
+            (
+                Some(ContainerState::Terminated),
+                terminated.reason,
+                terminated.message,
+                Some(terminated.exit_code),
+            )
+        } else if let Some(waiting) = state.waiting {
+            (
+                Some(ContainerState::Waiting),
+                waiting.reason,
+                waiting.message,
+                None,
+            )
+        } else if state.running.is_some() {
+            (Some(ContainerState::Running), None, None, None)
+        } else {
+            (None, None, None, None)
+        };
+        Self {
+            name: status.name,
+            state,
+            reason,
+            message,
+            exit_code,
+            restart_count: status.restart_count,
+        }
+    }
+}
+
+/// List the Kubernetes Events of a pod, ordered from oldest to newest
+pub(super) async fn list_pod_events(
+    client: Client,
+    namespace: &str,
+    pod_name: &str,
+) -> Result<Vec<PodEvent>, kube::Error> {
+    let api = Api::<Event>::namespaced(client, namespace);
+    let list_params = ListParams::default().fields(&format!(
+        "involvedObject.kind=Pod,involvedObject.name={pod_name}"
+    ));
+    let mut events = api
+        .list(&list_params)
+        .await?
+        .items
+        .into_iter()
+        .map(PodEvent::from)
+        .collect::<Vec<_>>();
+    events.sort_by_key(|event| event.last_timestamp);
+    Ok(events)
+}
+
+/// Get a pod, if it has not yet been garbage collected
+pub(super) async fn get_pod(
+    client: Client,
+    namespace: &str,
+    pod_name: &str,
+) -> Result<Option<Pod>, kube::Error> {
+    Api::<Pod>::namespaced(client, namespace)
+        .get_opt(pod_name)
+        .await
+}
+
+/// Get the conditions of a pod
+pub(super) fn pod_conditions(pod: &Pod) -> Vec<PodCondition> {
+    pod.status
+        .iter()
+        .flat_map(|status| status.conditions.iter().flatten())
+        .map(|condition| PodCondition {
+            type_: condition.type_.clone(),
+            status: condition.status.clone(),
+            reason: condition.reason.clone(),
+            message: condition.message.clone(),
+            last_transition_time: condition.last_transition_time.as_ref().map(|time| time.0),
+        })
+        .collect()
+}
+
+/// Get the statuses of the init and main containers of a pod
+pub(super) fn container_statuses(pod: &Pod) -> Vec<ContainerStatus> {
+    pod.status
+        .iter()
+        .flat_map(|status| {
+            status
+                .init_container_statuses
+                .iter()
+                .chain(status.container_statuses.iter())
+                .flatten()
+        })
+        .cloned()
+        .map(ContainerStatus::from)
+        .collect()
+}
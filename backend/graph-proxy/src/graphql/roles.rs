@@ -0,0 +1,301 @@
+use super::{auth_guard::AuthErrorCode, subscription::get_auth_token};
+use crate::validate_token::{TokenValidator, ValidatedAuthToken};
+use async_graphql::{Context, Error, ErrorExtensions, Guard, Result};
+use clap::Parser;
+use jsonwebtoken::dangerous::insecure_decode;
+use regex::Regex;
+use serde_json::Value;
+use std::{path::PathBuf, str::FromStr};
+
+/// Arguments for deriving the roles of a requester from the groups or roles in their token
+#[derive(Debug, Parser, Clone, Default)]
+pub struct RoleArgs {
+    /// The claim listing the groups or roles of the requester, with nested claims separated by dots
+    #[arg(long, env, default_value = "groups")]
+    pub role_claim: String,
+    /// A pattern matching the groups or roles of beamline staff, capturing the beamline as `instrument`
+    #[arg(long, env)]
+    pub beamline_staff_role_pattern: Option<Regex>,
+    /// A pattern matching the groups or roles of science group admins, capturing the science group
+    /// as `science_group`
+    #[arg(long, env)]
+    pub science_group_admin_role_pattern: Option<Regex>,
+    /// The instruments of each science group, each of the form `science_group=instrument,instrument`
+    #[arg(long, env, value_delimiter = ' ', num_args = 1..)]
+    pub science_group_instruments: Vec<ScienceGroupInstruments>,
+    /// The groups or roles of platform admins
+    #[arg(long, env, value_delimiter = ' ', num_args = 1..)]
+    pub platform_admin_roles: Vec<String>,
+    /// A file holding the bearer token used to query the Argo Server for staff and admin views,
+    /// the requester's own token is used if not set
+    #[arg(long, env)]
+    pub privileged_token_file: Option<PathBuf>,
+}
+
+/// The instruments belonging to a science group
+#[derive(Debug, Clone)]
+pub struct ScienceGroupInstruments {
+    /// The name of the science group, as captured from the role
+    pub science_group: String,
+    /// The instruments of the science group
+    pub instruments: Vec<String>,
+}
+
+impl FromStr for ScienceGroupInstruments {
+    type Err = anyhow::Error;
+
+    /// Parses instruments of the form `science_group=instrument,instrument`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (science_group, instruments) = value.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("Expected instruments of the form science_group=instrument,instrument")
+        })?;
+        Ok(Self {
+            science_group: science_group.to_owned(),
+            instruments: instruments.split(',').map(str::to_owned).collect(),
+        })
+    }
+}
+
+/// A role granting visibility beyond the visits of which the requester is a member
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Role {
+    /// Staff of a beamline, who may view the workflows of every visit on it
+    BeamlineStaff(String),
+    /// An admin of a science group, who may view the workflows of every visit on its instruments
+    ScienceGroupAdmin(Vec<String>),
+    /// An admin of the platform, who may view all workflows and triggers
+    PlatformAdmin,
+}
+
+/// The roles held by a requester
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct Roles(Vec<Role>);
+
+impl Roles {
+    /// Derive roles from the groups or roles listed in the configured claim
+    ///
+    /// Groups are only meaningful within the issuer they are configured for, so tokens of any
+    /// other issuer are granted no roles.
+    fn from_claims(args: &RoleArgs, primary_issuer: Option<&str>, claims: &Value) -> Self {
+        if primary_issuer.is_some_and(|issuer| claims["iss"].as_str() != Some(issuer)) {
+            return Self::default();
+        }
+        let claim = args
+            .role_claim
+            .split('.')
+            .try_fold(claims, |claims, name| claims.get(name));
+        let Some(Value::Array(groups)) = claim else {
+            return Self::default();
+        };
+        Self(
+            groups
+                .iter()
+                .filter_map(Value::as_str)
+                .filter_map(|group| {
+                    if args.platform_admin_roles.iter().any(|role| role == group) {
+                        Some(Role::PlatformAdmin)
+                    } else if let Some(science_group) = args
+                        .science_group_admin_role_pattern
+                        .as_ref()
+                        .and_then(|pattern| pattern.captures(group))
+                        .and_then(|captures| captures.name("science_group"))
+                    {
+                        Some(Role::ScienceGroupAdmin(
+                            args.science_group_instruments
+                                .iter()
+                                .filter(|instruments| {
+                                    instruments.science_group == science_group.as_str()
+                                })
+                                .flat_map(|instruments| instruments.instruments.clone())
+                                .collect(),
+                        ))
+                    } else {
+                        args.beamline_staff_role_pattern
+                            .as_ref()?
+                            .captures(group)?
+                            .name("instrument")
+                            .map(|instrument| Role::BeamlineStaff(instrument.as_str().to_owned()))
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether the requester may view all workflows and triggers
+    pub(super) fn is_platform_admin(&self) -> bool {
+        self.0.contains(&Role::PlatformAdmin)
+    }
+
+    /// Whether the requester may view the workflows of every visit on an instrument, as staff of
+    /// the instrument or an admin of its science group or the platform
+    pub(super) fn is_staff_of(&self, instrument: &str) -> bool {
+        self.0.iter().any(|role| match role {
+            Role::BeamlineStaff(staff_of) => staff_of == instrument,
+            Role::ScienceGroupAdmin(instruments) => {
+                instruments.iter().any(|admin_of| admin_of == instrument)
+            }
+            Role::PlatformAdmin => true,
+        })
+    }
+}
+
+/// The roles of the requester, none if roles are not configured, the token cannot be decoded or
+/// the token was not issued by the primary issuer of the [`TokenValidator`]
+pub(super) fn requester_roles(ctx: &Context<'_>) -> Roles {
+    let Some(args) = ctx.data_opt::<RoleArgs>() else {
+        return Roles::default();
+    };
+    let primary_issuer = ctx
+        .data_opt::<TokenValidator>()
+        .map(TokenValidator::primary_issuer);
+    ctx.data_opt::<ValidatedAuthToken>()
+        .and_then(ValidatedAuthToken::as_token)
+        .and_then(|token| insecure_decode::<Value>(token.token()).ok())
+        .map(|data| Roles::from_claims(args, primary_issuer, &data.claims))
+        .unwrap_or_default()
+}
+
+/// The bearer token with which staff and admin views query the Argo Server
+pub(super) async fn privileged_token(ctx: &Context<'_>) -> anyhow::Result<String> {
+    match ctx
+        .data_opt::<RoleArgs>()
+        .and_then(|args| args.privileged_token_file.as_ref())
+    {
+        Some(path) => Ok(tokio::fs::read_to_string(path).await?.trim().to_owned()),
+        None => get_auth_token(ctx),
+    }
+}
+
+/// An error reporting the requester lacks the role needed
+fn forbidden(message: impl Into<String>) -> Error {
+    Error::new(message).extend_with(|_, e| e.set("code", AuthErrorCode::Forbidden.to_string()))
+}
+
+/// Checks the requester is staff of an instrument, or an admin of its science group or the platform
+pub(super) struct StaffGuard {
+    /// The instrument whose staff are allowed
+    instrument: String,
+}
+
+impl StaffGuard {
+    /// Allow staff of the given instrument
+    pub(super) fn new(instrument: &str) -> Self {
+        Self {
+            instrument: instrument.to_owned(),
+        }
+    }
+}
+
+impl Guard for StaffGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        if requester_roles(ctx).is_staff_of(&self.instrument) {
+            Ok(())
+        } else {
+            Err(forbidden(format!(
+                "Authorization error: Only staff of {} may view its workflows",
+                self.instrument
+            )))
+        }
+    }
+}
+
+/// Checks the requester is a platform admin
+pub(super) struct AdminGuard;
+
+impl Guard for AdminGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        if requester_roles(ctx).is_platform_admin() {
+            Ok(())
+        } else {
+            Err(forbidden(
+                "Authorization error: Only platform admins may view this",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Role, RoleArgs, Roles, ScienceGroupInstruments};
+    use regex::Regex;
+    use rstest::rstest;
+    use serde_json::{json, Value};
+
+    fn role_args(role_claim: &str) -> RoleArgs {
+        RoleArgs {
+            role_claim: role_claim.to_string(),
+            beamline_staff_role_pattern: Some(
+                Regex::new("^/?(?<instrument>[a-z][0-9]+)_staff$").unwrap(),
+            ),
+            science_group_admin_role_pattern: Some(
+                Regex::new("^(?<science_group>[a-z]+)_admin$").unwrap(),
+            ),
+            science_group_instruments: vec!["mx=i03,i04".parse().unwrap()],
+            platform_admin_roles: vec!["workflows_admin".to_string()],
+            privileged_token_file: None,
+        }
+    }
+
+    #[rstest]
+    #[case("groups", json!({"groups": ["/i03_staff", "dls_staff"]}), vec![Role::BeamlineStaff("i03".to_string())])]
+    #[case("groups", json!({"groups": ["mx_admin"]}), vec![Role::ScienceGroupAdmin(vec!["i03".to_string(), "i04".to_string()])])]
+    #[case("groups", json!({"groups": ["soft_admin"]}), vec![Role::ScienceGroupAdmin(vec![])])]
+    #[case("realm_access.roles", json!({"realm_access": {"roles": ["workflows_admin", "i04_staff"]}}), vec![Role::PlatformAdmin, Role::BeamlineStaff("i04".to_string())])]
+    #[case("groups", json!({"realm_access": {"roles": ["workflows_admin"]}}), vec![])]
+    #[case("groups", json!({"groups": "workflows_admin"}), vec![])]
+    fn roles_are_derived_from_claims(
+        #[case] role_claim: &str,
+        #[case] claims: Value,
+        #[case] expected: Vec<Role>,
+    ) {
+        assert_eq!(
+            Roles::from_claims(&role_args(role_claim), None, &claims),
+            Roles(expected)
+        );
+    }
+
+    #[rstest]
+    #[case(json!({"iss": "https://authn.diamond.ac.uk/realms/master", "groups": ["workflows_admin"]}), vec![Role::PlatformAdmin])]
+    #[case(json!({"iss": "https://authn.diamond.ac.uk/realms/staging", "groups": ["workflows_admin"]}), vec![])]
+    #[case(json!({"groups": ["workflows_admin"]}), vec![])]
+    fn roles_are_only_derived_from_primary_issuer(
+        #[case] claims: Value,
+        #[case] expected: Vec<Role>,
+    ) {
+        assert_eq!(
+            Roles::from_claims(
+                &role_args("groups"),
+                Some("https://authn.diamond.ac.uk/realms/master"),
+                &claims
+            ),
+            Roles(expected)
+        );
+    }
+
+    #[test]
+    fn roles_grant_access_to_their_instruments() {
+        let staff = Roles(vec![Role::BeamlineStaff("i03".to_string())]);
+        assert!(staff.is_staff_of("i03"));
+        assert!(!staff.is_staff_of("i04"));
+        assert!(!staff.is_platform_admin());
+
+        let science_group_admin = Roles(vec![Role::ScienceGroupAdmin(vec![
+            "i03".to_string(),
+            "i04".to_string(),
+        ])]);
+        assert!(science_group_admin.is_staff_of("i04"));
+        assert!(!science_group_admin.is_staff_of("b21"));
+        assert!(!science_group_admin.is_platform_admin());
+
+        let platform_admin = Roles(vec![Role::PlatformAdmin]);
+        assert!(platform_admin.is_staff_of("b21"));
+        assert!(platform_admin.is_platform_admin());
+    }
+
+    #[test]
+    fn science_group_instruments_are_parsed() {
+        let instruments = "mx=i03,i04".parse::<ScienceGroupInstruments>().unwrap();
+        assert_eq!(instruments.science_group, "mx");
+        assert_eq!(instruments.instruments, vec!["i03", "i04"]);
+    }
+}
@@ -1,21 +1,34 @@
 use crate::graphql::AuthGuard;
-use argo_workflows_openapi::IoArgoprojWorkflowV1alpha1WorkflowWatchEvent;
-use async_graphql::{Context, SimpleObject, Subscription};
+use argo_workflows_openapi::{
+    APIResult, IoArgoprojWorkflowV1alpha1Workflow, IoArgoprojWorkflowV1alpha1WorkflowWatchEvent,
+};
+use async_graphql::{Context, Enum, SimpleObject, Subscription};
 use async_stream::stream;
+use chrono::{DateTime, Utc};
 use eventsource_stream::Eventsource;
 use futures_util::{Stream, StreamExt};
 use serde::Deserialize;
-use std::ops::Deref;
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    time::{Duration, Instant},
+};
+use url::Url;
 
 use crate::{
     graphql::{
-        workflows::{Workflow, WorkflowParsingError},
+        errors::CodedError,
+        filters::WorkflowFilter,
+        workflows::{task_pod_name, Workflow, WorkflowParsingError},
         VisitInput,
     },
     validate_token::ValidatedAuthToken,
     ArgoServerUrl,
 };
 
+/// The minimum time between lookups of the task running a pod whose task is not yet known
+const TASK_NAME_LOOKUP_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Subscribe to events involving workflows
 #[derive(Debug, Clone, Default)]
 pub struct WorkflowsSubscription;
@@ -31,21 +44,122 @@ pub struct LogEntry {
 
 /// A log response returned by the Argo logs API
 #[derive(Debug, Deserialize)]
-struct LogResponse {
+pub(super) struct LogResponse {
     /// The result of the log response
-    result: Option<LogContent>,
+    pub(super) result: Option<LogContent>,
 }
 
 /// The data from the log result returned by the Argo logs API
 #[derive(Debug, Deserialize)]
-struct LogContent {
+pub(super) struct LogContent {
     /// The log content
-    content: String,
+    pub(super) content: String,
     /// The name of the pod producing the log
     #[serde(rename = "podName")]
     pod_name: String,
 }
 
+/// A single log line streamed from one of the pods of a workflow
+#[derive(Debug, Clone, SimpleObject)]
+pub struct WorkflowLogEntry {
+    /// The log line content
+    content: String,
+    /// The name of the pod producing the log
+    pod_name: String,
+    /// The display name of the task producing the log
+    task_name: Option<String>,
+    /// The time at which the log line was produced
+    timestamp: Option<DateTime<Utc>>,
+}
+
+impl WorkflowLogEntry {
+    /// Create a [`WorkflowLogEntry`] from timestamped log content
+    fn new(content: LogContent, task_name: Option<String>) -> Self {
+        let (timestamp, line) = content
+            .content
+            .split_once(' ')
+            .and_then(|(timestamp, line)| {
+                Some((DateTime::parse_from_rfc3339(timestamp).ok()?, line))
+            })
+            .map_or((None, content.content.as_str()), |(timestamp, line)| {
+                (Some(timestamp.to_utc()), line)
+            });
+        WorkflowLogEntry {
+            content: line.to_string(),
+            pod_name: content.pod_name,
+            task_name,
+            timestamp,
+        }
+    }
+}
+
+/// The time at which the task of each pod with an unknown task was last looked up
+#[derive(Debug, Default)]
+struct TaskNameLookups(HashMap<String, Instant>);
+
+impl TaskNameLookups {
+    /// Whether the task of the pod should be looked up, recording the lookup if so
+    ///
+    /// Lookups of each pod are limited to one per [`TASK_NAME_LOOKUP_INTERVAL`].
+    fn is_due(&mut self, pod_name: &str, now: Instant) -> bool {
+        match self.0.get_mut(pod_name) {
+            Some(looked_up_at) if now.duration_since(*looked_up_at) < TASK_NAME_LOOKUP_INTERVAL => {
+                false
+            }
+            Some(looked_up_at) => {
+                *looked_up_at = now;
+                true
+            }
+            None => {
+                self.0.insert(pod_name.to_owned(), now);
+                true
+            }
+        }
+    }
+}
+
+impl From<LogContent> for LogEntry {
+    fn from(content: LogContent) -> Self {
+        LogEntry {
+            content: content.content,
+            pod_name: content.pod_name,
+        }
+    }
+}
+
+/// The kind of change made to a workflow
+#[derive(Debug, Enum, Copy, Clone, Eq, PartialEq)]
+enum WorkflowEventType {
+    /// The workflow was created
+    Added,
+    /// The workflow was updated
+    Updated,
+    /// The workflow was deleted
+    Deleted,
+}
+
+impl TryFrom<&str> for WorkflowEventType {
+    type Error = String;
+
+    fn try_from(event_type: &str) -> Result<Self, Self::Error> {
+        match event_type {
+            "ADDED" => Ok(Self::Added),
+            "MODIFIED" => Ok(Self::Updated),
+            "DELETED" => Ok(Self::Deleted),
+            _ => Err(format!("Unrecognised event type {event_type}")),
+        }
+    }
+}
+
+/// A change made to a workflow within a visit
+#[derive(Debug, SimpleObject)]
+struct WorkflowEvent {
+    /// The kind of change made to the workflow
+    event_type: WorkflowEventType,
+    /// The workflow after the change
+    workflow: Workflow,
+}
+
 /// Succees/fail events from Workflows API
 #[derive(Debug, Deserialize)]
 struct WatchEvent {
@@ -55,6 +169,22 @@ struct WatchEvent {
     error: Option<StreamError>,
 }
 
+impl WatchEvent {
+    /// The successful event, or the error returned by the API
+    fn into_result(self) -> Result<IoArgoprojWorkflowV1alpha1WorkflowWatchEvent, CodedError> {
+        match (self.result, self.error) {
+            (Some(result), None) => Ok(result),
+            (None, Some(err)) => Err(CodedError::from_grpc_code(err.code, err.message)),
+            (None, None) => Err(CodedError::upstream_unavailable(
+                "Missing result and error in event",
+            )),
+            (Some(_), Some(_)) => Err(CodedError::upstream_unavailable(
+                "Conflicting result and error in event",
+            )),
+        }
+    }
+}
+
 /// Get authentication token
 pub fn get_auth_token(ctx: &Context<'_>) -> anyhow::Result<String> {
     let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
@@ -66,31 +196,32 @@ pub fn get_auth_token(ctx: &Context<'_>) -> anyhow::Result<String> {
 
 #[Subscription(guard = "AuthGuard")]
 impl WorkflowsSubscription {
-    /// Processing to subscribe to logs for a single pod of a workflow
+    /// Processing to subscribe to logs for a single task of a workflow, identified by its task ID
     async fn logs(
         &self,
         ctx: &Context<'_>,
         visit: VisitInput,
         workflow_name: String,
         task_id: String,
-    ) -> anyhow::Result<impl Stream<Item = Result<LogEntry, String>>> {
+    ) -> anyhow::Result<impl Stream<Item = Result<LogEntry, CodedError>>> {
         let auth_token = get_auth_token(ctx)?;
 
         let namespace = visit.to_string();
         let server_url = ctx.data_unchecked::<ArgoServerUrl>().deref().clone();
-        let mut url = server_url;
+        let mut workflow_url = server_url;
+        workflow_url
+            .path_segments_mut()
+            .expect("Invalid base URL")
+            .extend(["api", "v1", "workflows", &namespace, &workflow_name]);
+        let pod_name = resolve_pod_name(workflow_url.clone(), &auth_token, &task_id).await?;
 
-        url.path_segments_mut().expect("Invalid base URL").extend([
-            "api",
-            "v1",
-            "workflows",
-            &namespace,
-            &workflow_name,
-            "log",
-        ]);
+        let mut url = workflow_url;
+        url.path_segments_mut()
+            .expect("Invalid base URL")
+            .push("log");
 
         url.query_pairs_mut()
-            .append_pair("podName", &task_id)
+            .append_pair("podName", &pod_name)
             .append_pair("logOptions.container", "main")
             .append_pair("logOptions.follow", "true");
 
@@ -113,25 +244,110 @@ impl WorkflowsSubscription {
                             match serde_json::from_str::<LogResponse>(line) {
                                 Ok(parsed) => {
                                     if let Some(result) = parsed.result {
-                                        yield Ok(LogEntry {
-                                            content: result.content,
-                                            pod_name: result.pod_name,
-                                        });
+                                        yield Ok(result.into());
                                     } else {
-                                        yield Err("Missing result in log response".to_string());
+                                        yield Err(CodedError::upstream_unavailable("Missing result in log response"));
                                     }
                                 }
                                 Err(_) => {
                                     yield Ok(LogEntry {
                                         content: line.trim().to_string(),
-                                        pod_name: task_id.clone(),
+                                        pod_name: pod_name.clone(),
                                     });
                                 }
                             }
                         }
                     }
-                    Ok(_) | Err(_) => {
-                        yield Err("Failed to read log chunk".to_string());
+                    Ok(_) => {
+                        yield Err(CodedError::from_status(status, "Failed to read log chunk"));
+                    }
+                    Err(_) => {
+                        yield Err(CodedError::upstream_unavailable("Failed to read log chunk"));
+                    }
+                }
+            }
+        };
+
+        Ok(log_stream)
+    }
+
+    /// Processing to subscribe to logs for every pod of a workflow, including pods started after subscribing
+    async fn workflow_logs(
+        &self,
+        ctx: &Context<'_>,
+        visit: VisitInput,
+        workflow_name: String,
+        #[graphql(default = "main")] container: String,
+    ) -> anyhow::Result<impl Stream<Item = Result<WorkflowLogEntry, CodedError>>> {
+        let auth_token = get_auth_token(ctx)?;
+
+        let namespace = visit.to_string();
+        let mut workflow_url = ctx.data_unchecked::<ArgoServerUrl>().deref().clone();
+        workflow_url
+            .path_segments_mut()
+            .expect("Invalid base URL")
+            .extend(["api", "v1", "workflows", &namespace, &workflow_name]);
+        let mut task_names = fetch_task_names(workflow_url.clone(), &auth_token).await?;
+
+        let mut url = workflow_url.clone();
+        url.path_segments_mut()
+            .expect("Invalid base URL")
+            .push("log");
+        url.query_pairs_mut()
+            .append_pair("logOptions.container", &container)
+            .append_pair("logOptions.follow", "true")
+            .append_pair("logOptions.timestamps", "true");
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(url)
+            .bearer_auth(&auth_token)
+            .header("Accept", "text/plain")
+            .send()
+            .await?;
+
+        let status = response.status();
+        let byte_stream = response.bytes_stream();
+        let log_stream = stream! {
+            let mut buffer = Vec::new();
+            let mut task_name_lookups = TaskNameLookups::default();
+            for await chunk_result in byte_stream {
+                match chunk_result {
+                    Ok(chunk) if status.is_success() => {
+                        buffer.extend_from_slice(&chunk);
+                        while let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+                            let line = buffer.drain(..=end).collect::<Vec<_>>();
+                            let line = String::from_utf8_lossy(&line);
+                            if line.trim().is_empty() {
+                                continue;
+                            }
+                            let Some(result) = serde_json::from_str::<LogResponse>(&line)
+                                .ok()
+                                .and_then(|parsed| parsed.result)
+                            else {
+                                yield Err(CodedError::upstream_unavailable("Missing result in log response"));
+                                continue;
+                            };
+                            // Pods started after subscribing are resolved by refreshing the workflow,
+                            // retried until the node status of the pod has been published
+                            if !task_names.contains_key(&result.pod_name)
+                                && task_name_lookups.is_due(&result.pod_name, Instant::now())
+                            {
+                                if let Ok(refreshed) =
+                                    fetch_task_names(workflow_url.clone(), &auth_token).await
+                                {
+                                    task_names = refreshed;
+                                }
+                            }
+                            let task_name = task_names.get(&result.pod_name).cloned();
+                            yield Ok(WorkflowLogEntry::new(result, task_name));
+                        }
+                    }
+                    Ok(_) => {
+                        yield Err(CodedError::from_status(status, "Failed to read log chunk"));
+                    }
+                    Err(_) => {
+                        yield Err(CodedError::upstream_unavailable("Failed to read log chunk"));
                     }
                 }
             }
@@ -140,13 +356,77 @@ impl WorkflowsSubscription {
         Ok(log_stream)
     }
 
+    /// Processing to subscribe to changes of every workflow in a visit
+    async fn workflows(
+        &self,
+        ctx: &Context<'_>,
+        visit: VisitInput,
+        filter: Option<WorkflowFilter>,
+    ) -> anyhow::Result<impl Stream<Item = Result<WorkflowEvent, CodedError>>> {
+        let auth_token = get_auth_token(ctx)?;
+
+        let session = visit.to_string();
+        let mut url = ctx.data_unchecked::<ArgoServerUrl>().deref().clone();
+        url.path_segments_mut().expect("Invalid base URL").extend([
+            "api",
+            "v1",
+            "workflow-events",
+            &session,
+        ]);
+        let filter = filter.unwrap_or_default();
+        filter.generate_filters(&mut url);
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(url)
+            .bearer_auth(auth_token)
+            .header("Accept", "text/event-stream")
+            .send()
+            .await?
+            .bytes_stream()
+            .eventsource();
+
+        let stream = response.filter_map(move |event_result| {
+            let event = match event_result {
+                Ok(event) => serde_json::from_str::<WatchEvent>(&event.data)
+                    .map_err(|err| CodedError::upstream_unavailable(err.to_string()))
+                    .and_then(WatchEvent::into_result),
+                Err(_err) => Err(CodedError::upstream_unavailable(
+                    "Failed to read event from stream",
+                )),
+            };
+            let event = match event {
+                Ok(event) => match (event.type_.as_deref(), event.object) {
+                    (Some(event_type), Some(workflow)) if filter.matches(&workflow) => {
+                        WorkflowEventType::try_from(event_type)
+                            .map(|event_type| {
+                                Some(WorkflowEvent {
+                                    event_type,
+                                    workflow: Workflow::new(workflow, visit.clone().into()),
+                                })
+                            })
+                            .map_err(CodedError::upstream_unavailable)
+                    }
+                    (Some(_), Some(_)) => Ok(None),
+                    _ => Err(CodedError::upstream_unavailable(
+                        "No workflow object returned",
+                    )),
+                },
+                Err(err) => Err(err),
+            };
+            async move { event.transpose() }
+        });
+
+        Ok(stream)
+    }
+
     /// Processing to subscribe to data for all workflows in a session
     async fn workflow(
         &self,
         ctx: &Context<'_>,
         visit: VisitInput,
         name: String,
-    ) -> anyhow::Result<impl Stream<Item = Result<Workflow, String>>> {
+    ) -> anyhow::Result<impl Stream<Item = Result<Workflow, CodedError>>> {
         let auth_token = get_auth_token(ctx)?;
 
         let session = visit.to_string();
@@ -180,25 +460,19 @@ impl WorkflowsSubscription {
             async move {
                 match event_result {
                     Ok(event) => {
-                        let watch_event: WatchEvent =
-                            serde_json::from_str(&event.data).map_err(|e| e.to_string())?;
-
-                        match (watch_event.result, watch_event.error) {
-                            (Some(result), None) => {
-                                if let Some(workflow) = result.object {
-                                    Ok(Workflow::new(workflow, session_clone.into()))
-                                } else {
-                                    Err("No workflow object returned".to_string())
-                                }
-                            }
-                            (None, Some(err)) => Err(err.message),
-                            (None, None) => Err("Missing result and error in event".to_string()),
-                            (Some(_), Some(_)) => {
-                                Err("Conflicting result and error in event".to_string())
-                            }
+                        let watch_event: WatchEvent = serde_json::from_str(&event.data)
+                            .map_err(|e| CodedError::upstream_unavailable(e.to_string()))?;
+
+                        match watch_event.into_result()?.object {
+                            Some(workflow) => Ok(Workflow::new(workflow, session_clone.into())),
+                            None => Err(CodedError::upstream_unavailable(
+                                "No workflow object returned",
+                            )),
                         }
                     }
-                    Err(_err) => Err("Failed to read event from stream".to_string()),
+                    Err(_err) => Err(CodedError::upstream_unavailable(
+                        "Failed to read event from stream",
+                    )),
                 }
             }
         });
@@ -207,9 +481,57 @@ impl WorkflowsSubscription {
     }
 }
 
+/// Get the name of the pod running a task of a workflow
+///
+/// Falls back to the task ID, which is also the pod name under the v1 pod name format.
+async fn resolve_pod_name(url: Url, auth_token: &str, task_id: &str) -> anyhow::Result<String> {
+    let workflow = reqwest::Client::new()
+        .get(url)
+        .bearer_auth(auth_token)
+        .send()
+        .await?
+        .json::<APIResult<IoArgoprojWorkflowV1alpha1Workflow>>()
+        .await?
+        .into_result()?;
+    Ok(workflow
+        .status
+        .as_ref()
+        .and_then(|status| status.nodes.get(task_id))
+        .map(|node| task_pod_name(&workflow, node))
+        .unwrap_or_else(|| task_id.to_string()))
+}
+
+/// Get the display names of the tasks of a workflow, keyed by the name of the pod running them
+async fn fetch_task_names(url: Url, auth_token: &str) -> anyhow::Result<HashMap<String, String>> {
+    let workflow = reqwest::Client::new()
+        .get(url)
+        .bearer_auth(auth_token)
+        .send()
+        .await?
+        .json::<APIResult<IoArgoprojWorkflowV1alpha1Workflow>>()
+        .await?
+        .into_result()?;
+    Ok(workflow
+        .status
+        .iter()
+        .flat_map(|status| status.nodes.values())
+        .filter(|node| node.type_ == "Pod")
+        .map(|node| {
+            (
+                task_pod_name(&workflow, node),
+                node.display_name
+                    .clone()
+                    .unwrap_or_else(|| node.name.clone()),
+            )
+        })
+        .collect())
+}
+
 /// Struct for storing message of StreamError
 #[derive(Debug, Deserialize)]
 struct StreamError {
+    /// The gRPC code of the error
+    code: Option<i32>,
     /// The message associated with the error
     message: String,
 }
@@ -334,6 +656,378 @@ mod tests {
         workflow_events_endpoint.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn workflows_subscription_returns_filtered_events() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let workflow_value = |name: &str| {
+            let mut workflow_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            workflow_file_path.push("test-assets");
+            workflow_file_path.push(format!("get-workflow-{name}.json"));
+            let workflow_json = fs::read_to_string(&workflow_file_path)
+                .expect("failed to read workflow test asset");
+            serde_json::from_str::<Value>(&workflow_json)
+                .expect("workflow fixture is not valid JSON")
+        };
+        let sse_body = [
+            ("ADDED", "wdkwj"),
+            ("MODIFIED", "n6jsg"),
+            ("DELETED", "wdkwj"),
+        ]
+        .iter()
+        .map(|(event_type, name)| {
+            let event_payload = json!({
+                "result": {
+                    "type": event_type,
+                    "object": workflow_value(name)
+                },
+                "error": null
+            });
+            format!(
+                "data: {}\n\n",
+                serde_json::to_string(&event_payload).expect("failed to serialize SSE payload")
+            )
+        })
+        .collect::<String>();
+
+        let mut server = mockito::Server::new_async().await;
+        let path = format!("/api/v1/workflow-events/{visit}");
+        let workflow_events_endpoint = server
+            .mock("GET", path.as_str())
+            .match_query(Matcher::UrlEncoded(
+                "listOptions.labelSelector".into(),
+                "workflows.argoproj.io/phase in (Succeeded)".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(sse_body)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+
+        let request = Request::new(format!(
+            r#"
+        subscription {{
+            workflows(
+                visit: {{ proposalCode: "{}", proposalNumber: {}, number: {} }},
+                filter: {{ workflowStatusFilter: {{ succeeded: true }}, namePrefix: "numpy-benchmark-w" }}
+            ) {{
+                eventType
+                workflow {{
+                    name
+                }}
+            }}
+        }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number
+        ));
+
+        let responses = schema
+            .execute_stream(request)
+            .map(|response| response.data.into_json().expect("invalid response json"))
+            .collect::<Vec<_>>()
+            .await;
+
+        let expected_data = vec![
+            json!({
+                "workflows": {
+                    "eventType": "ADDED",
+                    "workflow": {"name": "numpy-benchmark-wdkwj"}
+                }
+            }),
+            json!({
+                "workflows": {
+                    "eventType": "DELETED",
+                    "workflow": {"name": "numpy-benchmark-wdkwj"}
+                }
+            }),
+        ];
+        assert_eq!(responses, expected_data);
+
+        workflow_events_endpoint.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn workflows_subscription_errors_are_coded() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+        let sse_body = [
+            json!({"result": null, "error": {"code": 7, "message": "namespace is forbidden"}}),
+            json!({"result": null, "error": null}),
+        ]
+        .iter()
+        .map(|event_payload| format!("data: {event_payload}\n\n"))
+        .collect::<String>();
+
+        let mut server = mockito::Server::new_async().await;
+        let workflow_events_endpoint = server
+            .mock("GET", format!("/api/v1/workflow-events/{visit}").as_str())
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(sse_body)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+
+        let request = Request::new(format!(
+            r#"
+        subscription {{
+            workflows(
+                visit: {{ proposalCode: "{}", proposalNumber: {}, number: {} }}
+            ) {{
+                eventType
+            }}
+        }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number
+        ));
+
+        let codes = schema
+            .execute_stream(request)
+            .map(|response| {
+                response.errors[0]
+                    .extensions
+                    .as_ref()
+                    .and_then(|extensions| extensions.get("code"))
+                    .cloned()
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            codes,
+            vec![
+                Some(async_graphql::Value::from("FORBIDDEN")),
+                Some(async_graphql::Value::from("UPSTREAM_UNAVAILABLE")),
+            ]
+        );
+        workflow_events_endpoint.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn logs_subscription_resolves_pod_name_of_task() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+        let workflow = json!({
+            "metadata": {"name": workflow_name, "uid": "c5b8d7f4-6f0b-4c2f-9d44-bb8d2f1f3a10"},
+            "spec": {},
+            "status": {
+                "nodes": {
+                    "numpy-benchmark-wdkwj-3333639851": {
+                        "id": "numpy-benchmark-wdkwj-3333639851",
+                        "name": "numpy-benchmark-wdkwj.benchmark",
+                        "templateName": "benchmark",
+                        "type": "Pod"
+                    }
+                }
+            }
+        });
+
+        let mut server = mockito::Server::new_async().await;
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                format!("/api/v1/workflows/{visit}/{workflow_name}").as_str(),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(workflow.to_string())
+            .create_async()
+            .await;
+        let logs_endpoint = server
+            .mock(
+                "GET",
+                format!("/api/v1/workflows/{visit}/{workflow_name}/log").as_str(),
+            )
+            .match_query(Matcher::UrlEncoded(
+                "podName".into(),
+                "numpy-benchmark-wdkwj-benchmark-3333639851".into(),
+            ))
+            .with_status(200)
+            .with_body(format!(
+                "{}\n",
+                json!({"result": {
+                    "content": "Benchmarking numpy",
+                    "podName": "numpy-benchmark-wdkwj-benchmark-3333639851"
+                }})
+            ))
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+
+        let request = Request::new(format!(
+            r#"
+        subscription {{
+            logs(
+                workflowName: "{}",
+                visit: {{ proposalCode: "{}", proposalNumber: {}, number: {} }},
+                taskId: "numpy-benchmark-wdkwj-3333639851"
+            ) {{
+                content
+                podName
+            }}
+        }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        ));
+
+        let responses = schema
+            .execute_stream(request)
+            .map(|response| response.data.into_json().expect("invalid response json"))
+            .collect::<Vec<_>>()
+            .await;
+
+        workflow_endpoint.assert_async().await;
+        logs_endpoint.assert_async().await;
+        assert_eq!(
+            responses,
+            vec![json!({
+                "logs": {
+                    "content": "Benchmarking numpy",
+                    "podName": "numpy-benchmark-wdkwj-benchmark-3333639851"
+                }
+            })]
+        );
+    }
+
+    #[tokio::test]
+    async fn workflow_logs_subscription_tags_lines_with_task() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut workflow_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        workflow_file_path.push("test-assets");
+        workflow_file_path.push("get-workflow-wdkwj.json");
+
+        let log_body = [
+            (workflow_name, "2024-11-19T09:45:50.123Z Benchmarking numpy"),
+            (
+                "numpy-benchmark-wdkwj-cleanup-1234",
+                "2024-11-19T09:46:55Z Cleaning up",
+            ),
+        ]
+        .iter()
+        .map(|(pod_name, content)| {
+            format!(
+                "{}\n",
+                json!({"result": {"content": content, "podName": pod_name}})
+            )
+        })
+        .collect::<String>();
+
+        let mut server = mockito::Server::new_async().await;
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                format!("/api/v1/workflows/{visit}/{workflow_name}").as_str(),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(workflow_file_path)
+            .expect(2)
+            .create_async()
+            .await;
+        let logs_endpoint = server
+            .mock(
+                "GET",
+                format!("/api/v1/workflows/{visit}/{workflow_name}/log").as_str(),
+            )
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("logOptions.container".into(), "main".into()),
+                Matcher::UrlEncoded("logOptions.follow".into(), "true".into()),
+                Matcher::UrlEncoded("logOptions.timestamps".into(), "true".into()),
+            ]))
+            .with_status(200)
+            .with_body(log_body)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+
+        let request = Request::new(format!(
+            r#"
+        subscription {{
+            workflowLogs(
+                workflowName: "{}",
+                visit: {{ proposalCode: "{}", proposalNumber: {}, number: {} }}
+            ) {{
+                content
+                podName
+                taskName
+                timestamp
+            }}
+        }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        ));
+
+        let responses = schema
+            .execute_stream(request)
+            .map(|response| response.data.into_json().expect("invalid response json"))
+            .collect::<Vec<_>>()
+            .await;
+
+        let expected_data = vec![
+            json!({
+                "workflowLogs": {
+                    "content": "Benchmarking numpy",
+                    "podName": workflow_name,
+                    "taskName": workflow_name,
+                    "timestamp": "2024-11-19T09:45:50.123+00:00"
+                }
+            }),
+            json!({
+                "workflowLogs": {
+                    "content": "Cleaning up",
+                    "podName": "numpy-benchmark-wdkwj-cleanup-1234",
+                    "taskName": null,
+                    "timestamp": "2024-11-19T09:46:55+00:00"
+                }
+            }),
+        ];
+        assert_eq!(responses, expected_data);
+
+        workflow_endpoint.assert_async().await;
+        logs_endpoint.assert_async().await;
+    }
+
     #[tokio::test]
     #[rstest]
     #[case(ValidatedAuthToken::Missing)]
@@ -386,4 +1080,19 @@ mod tests {
         let expected_value = json!(AuthErrorCode::Unauthenticated.to_string());
         assert_eq!(error_code, expected_value);
     }
+
+    #[test]
+    fn task_name_lookups_are_retried_at_a_limited_rate() {
+        use super::{TaskNameLookups, TASK_NAME_LOOKUP_INTERVAL};
+        use std::time::{Duration, Instant};
+
+        let mut lookups = TaskNameLookups::default();
+        let start = Instant::now();
+
+        assert!(lookups.is_due("pod-a", start));
+        assert!(!lookups.is_due("pod-a", start + Duration::from_secs(1)));
+        assert!(lookups.is_due("pod-b", start + Duration::from_secs(1)));
+        assert!(lookups.is_due("pod-a", start + TASK_NAME_LOOKUP_INTERVAL));
+        assert!(!lookups.is_due("pod-a", start + TASK_NAME_LOOKUP_INTERVAL));
+    }
 }
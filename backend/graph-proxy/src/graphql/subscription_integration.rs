@@ -48,6 +48,7 @@ use opentelemetry::KeyValue;
 use tower_service::Service;
 
 use crate::{
+    graphql::{limits::rate_limited_error, RateLimiter},
     metrics::MetricsState,
     validate_token::{TokenValidator, ValidationMethod},
 };
@@ -84,6 +85,8 @@ pub struct GraphQLSubscription<E> {
     executor: E,
     metrics_state: MetricsState,
     token_validator: Arc<TokenValidator>,
+    validation_method: ValidationMethod,
+    rate_limiter: Option<RateLimiter>,
 }
 
 impl<E> Clone for GraphQLSubscription<E>
@@ -95,6 +98,8 @@ where
             executor: self.executor.clone(),
             metrics_state: self.metrics_state.clone(),
             token_validator: self.token_validator.clone(),
+            validation_method: self.validation_method,
+            rate_limiter: self.rate_limiter.clone(),
         }
     }
 }
@@ -104,15 +109,21 @@ where
     E: Executor,
 {
     /// Create a GraphQL subscription service.
+    ///
+    /// Each connection takes a request from the rate limit of its subject, if one is given.
     pub fn new(
         executor: E,
         metrics_state: MetricsState,
         token_validator: Arc<TokenValidator>,
+        validation_method: ValidationMethod,
+        rate_limiter: Option<RateLimiter>,
     ) -> Self {
         Self {
             executor,
             metrics_state,
             token_validator,
+            validation_method,
+            rate_limiter,
         }
     }
 }
@@ -134,6 +145,8 @@ where
         let executor = self.executor.clone();
         let metrics = self.metrics_state.clone();
         let token_validator = self.token_validator.clone();
+        let validation_method = self.validation_method;
+        let rate_limiter = self.rate_limiter.clone();
 
         Box::pin(async move {
             let metrics = metrics;
@@ -186,8 +199,16 @@ where
                                 Ok(header) => {
                                     let mut data = Data::default();
                                     let validated_token = token_validator
-                                        .validate_token(Some(header), ValidationMethod::Jwt)
+                                        .validate_token(Some(header), validation_method)
                                         .await;
+                                    if let Some(rate_limiter) = &rate_limiter {
+                                        if !rate_limiter.check(&validated_token).await {
+                                            metrics
+                                                .total_errors
+                                                .add(1, &[KeyValue::new("status", "rate_limited")]);
+                                            return Err(rate_limited_error());
+                                        }
+                                    }
                                     data.insert(validated_token);
                                     Ok(data)
                                 }
@@ -453,6 +474,7 @@ mod tests {
     }
 
     async fn start_echo_token_server(
+        rate_limiter: Option<RateLimiter>,
     ) -> anyhow::Result<(String, tokio::task::JoinSet<()>, TestOidcServer)> {
         let oidc_server = TestOidcServer::new().await?;
 
@@ -473,6 +495,8 @@ mod tests {
                     schema.clone(),
                     MetricsState::new(Metrics::new(&NoopMeterProvider::new())),
                     Arc::new(token_validator),
+                    ValidationMethod::Jwt,
+                    rate_limiter,
                 )),
             )
             .with_state(schema.clone());
@@ -498,7 +522,7 @@ mod tests {
             return Ok(());
         }
 
-        let (server_address, _server_guard, oidc_server) = start_echo_token_server().await?;
+        let (server_address, _server_guard, oidc_server) = start_echo_token_server(None).await?;
 
         let access_token = oidc_server.access_token().await?;
 
@@ -537,7 +561,7 @@ mod tests {
             return Ok(());
         }
 
-        let (server_address, _server_guard, oidc_server) = start_echo_token_server().await?;
+        let (server_address, _server_guard, oidc_server) = start_echo_token_server(None).await?;
 
         let access_token = oidc_server.access_token().await?;
 
@@ -576,7 +600,7 @@ mod tests {
             return Ok(());
         }
 
-        let (server_address, _server_guard, oidc_server) = start_echo_token_server().await?;
+        let (server_address, _server_guard, oidc_server) = start_echo_token_server(None).await?;
 
         let mut req = server_address.into_client_request()?;
         req.headers_mut().insert(
@@ -601,4 +625,47 @@ mod tests {
         oidc_server.stop().await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn subscription_connections_are_rate_limited() -> anyhow::Result<()> {
+        if std::env::var("WORKFLOWS_DEV_CONTAINER").is_ok() {
+            eprintln!("Skipping test: test containers don't work inside VSCode dev container");
+            return Ok(());
+        }
+
+        let (server_address, _server_guard, oidc_server) =
+            start_echo_token_server(Some(RateLimiter::new(0.001, 1))).await?;
+
+        let access_token = oidc_server.access_token().await?;
+        let mut results = Vec::new();
+        for _ in 0..2 {
+            let mut req = server_address.clone().into_client_request()?;
+            req.headers_mut().typed_insert(access_token.clone());
+            req.headers_mut().insert(
+                http::header::SEC_WEBSOCKET_PROTOCOL,
+                http::HeaderValue::from_static("graphql-transport-ws"),
+            );
+            let (ws, _resp) = tokio_tungstenite::connect_async(req).await?;
+            let client = graphql_ws_client::Client::build(ws);
+            results.push(
+                client
+                    .subscribe(StreamingOperation::<TestGraphQlTokenQuery>::new(()))
+                    .await,
+            );
+        }
+
+        assert!(
+            results[0].is_ok(),
+            "the first connection is within the limit"
+        );
+        assert!(
+            matches!(
+                &results[1],
+                Err(graphql_ws_client::Error::Close(_, msg)) if msg == "Rate limit exceeded, please retry later"
+            ),
+            "connections beyond the rate limit of the subject are refused"
+        );
+        oidc_server.stop().await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,138 @@
+use argo_workflows_openapi::IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplate;
+use axum::http::Uri;
+use futures_util::{FutureExt, StreamExt};
+use kube::{
+    api::{ApiResource, DynamicObject},
+    core::GroupVersionKind,
+    runtime::{
+        reflector::{self, ObjectRef, Store},
+        watcher, WatchStreamExt,
+    },
+    Api, Client, Config,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+/// The namespace in which ArgoCD Applications are stored
+const ARGOCD_NAMESPACE: &str = "argocd";
+
+/// A cache of ClusterWorkflowTemplates and ArgoCD Applications, kept up to date by watching the Kubernetes API
+#[derive(Clone)]
+pub struct TemplateCache {
+    /// ClusterWorkflowTemplates, keyed by name
+    templates: Store<DynamicObject>,
+    /// The API resource of ClusterWorkflowTemplates
+    templates_resource: ApiResource,
+    /// ArgoCD Applications in the [`ARGOCD_NAMESPACE`], keyed by name
+    applications: Store<DynamicObject>,
+    /// The API resource of ArgoCD Applications
+    applications_resource: ApiResource,
+}
+
+impl std::fmt::Debug for TemplateCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TemplateCache")
+            .field("templates", &self.templates.len())
+            .field("applications", &self.applications.len())
+            .finish()
+    }
+}
+
+impl TemplateCache {
+    /// Creates the cache and spawns the reflectors which populate it
+    pub async fn spawn(kubernetes_api_url: &Uri) -> anyhow::Result<Self> {
+        let mut config = Config::infer().await?;
+        config.cluster_url = kubernetes_api_url.clone();
+        let client = Client::try_from(config)?;
+
+        let templates_resource = ApiResource::from_gvk_with_plural(
+            &GroupVersionKind::gvk("argoproj.io", "v1alpha1", "ClusterWorkflowTemplate"),
+            "clusterworkflowtemplates",
+        );
+        let templates = reflect(
+            Api::all_with(client.clone(), &templates_resource),
+            templates_resource.clone(),
+        );
+        let applications_resource = ApiResource::from_gvk_with_plural(
+            &GroupVersionKind::gvk("argoproj.io", "v1alpha1", "Application"),
+            "applications",
+        );
+        let applications = reflect(
+            Api::namespaced_with(client, ARGOCD_NAMESPACE, &applications_resource),
+            applications_resource.clone(),
+        );
+
+        Ok(Self {
+            templates,
+            templates_resource,
+            applications,
+            applications_resource,
+        })
+    }
+
+    /// Whether the initial list of both resources has completed
+    pub(super) fn is_ready(&self) -> bool {
+        [&self.templates, &self.applications].iter().all(|store| {
+            store
+                .wait_until_ready()
+                .now_or_never()
+                .is_some_and(|ready| ready.is_ok())
+        })
+    }
+
+    /// Get a ClusterWorkflowTemplate by name
+    pub(super) fn template(
+        &self,
+        name: &str,
+    ) -> Option<IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplate> {
+        let object_ref = ObjectRef::new_with(name, self.templates_resource.clone());
+        self.templates
+            .get(&object_ref)
+            .and_then(|template| to_cluster_workflow_template(&template))
+    }
+
+    /// Get all ClusterWorkflowTemplates, sorted by name
+    pub(super) fn templates(&self) -> Vec<IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplate> {
+        let mut templates = self
+            .templates
+            .state()
+            .iter()
+            .filter_map(|template| to_cluster_workflow_template(template))
+            .collect::<Vec<_>>();
+        templates.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+        templates
+    }
+
+    /// Get an ArgoCD Application by name
+    pub(super) fn application(&self, name: &str) -> Option<Arc<DynamicObject>> {
+        let object_ref =
+            ObjectRef::new_with(name, self.applications_resource.clone()).within(ARGOCD_NAMESPACE);
+        self.applications.get(&object_ref)
+    }
+}
+
+/// Spawns a reflector which mirrors the resources of the API into the returned [`Store`]
+fn reflect(api: Api<DynamicObject>, resource: ApiResource) -> Store<DynamicObject> {
+    let writer = reflector::store::Writer::new(resource);
+    let store = writer.as_reader();
+    let stream = reflector::reflector(
+        writer,
+        watcher(api, watcher::Config::default()).default_backoff(),
+    );
+    tokio::spawn(stream.for_each(|event| async move {
+        if let Err(err) = event {
+            warn!("Template cache watch failed: {err}");
+        }
+    }));
+    store
+}
+
+/// Converts a dynamically typed ClusterWorkflowTemplate into the Argo Workflows type
+fn to_cluster_workflow_template(
+    object: &DynamicObject,
+) -> Option<IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplate> {
+    serde_json::to_value(object)
+        .and_then(serde_json::from_value)
+        .inspect_err(|err| warn!("Could not parse cached ClusterWorkflowTemplate: {err}"))
+        .ok()
+}
@@ -1,7 +1,13 @@
 use std::{collections::BTreeMap, ops::Deref};
 
 use crate::{
-    graphql::{auth_guard::AuthGuard, subscription::get_auth_token, VisitInput},
+    graphql::{
+        auth_guard::AuthGuard,
+        errors::CodedError,
+        roles::{requester_roles, AdminGuard},
+        subscription::get_auth_token,
+        VisitInput,
+    },
     KubernetesApiUrl,
 };
 use async_graphql::{
@@ -20,7 +26,7 @@ use serde_json::Value;
 /// An error relating to a workflow Trigger
 #[derive(Debug, thiserror::Error)]
 #[allow(clippy::missing_docs_in_private_items)]
-enum TriggerError {
+pub(super) enum TriggerError {
     #[error(r#"Unable to decode JWT"#)]
     TokenDecodeError,
     #[error(r#"Posix UID missing from token claims"#)]
@@ -76,7 +82,7 @@ impl From<Trigger> for TriggerGQL {
 }
 
 /// Infers the Kubernetes client
-async fn setup_client(ctx: &Context<'_>) -> Result<Client, TriggerError> {
+pub(super) async fn setup_client(ctx: &Context<'_>) -> Result<Client, TriggerError> {
     let kubernetes_api_url = ctx.data_unchecked::<KubernetesApiUrl>();
     let mut config = Config::infer()
         .await
@@ -106,6 +112,8 @@ pub struct TriggerQuery;
 #[Object(guard = "AuthGuard")]
 impl TriggerQuery {
     /// Get a specific Trigger by name and visit
+    ///
+    /// Only the creator of the Trigger, or a platform admin, may get it.
     async fn trigger(
         &self,
         ctx: &Context<'_>,
@@ -117,10 +125,12 @@ impl TriggerQuery {
         let api: Api<Trigger> = Api::namespaced(client, &visit.unwrap_or("events".to_string()));
         match api.get(&name).await {
             Ok(trigger) => {
-                if trigger.clone().metadata.labels.is_some_and(|f| {
-                    f.get("workflows.diamond.ac.uk/posixuid")
-                        .is_some_and(|l| l == &posix_uid)
-                }) {
+                if requester_roles(ctx).is_platform_admin()
+                    || trigger.clone().metadata.labels.is_some_and(|f| {
+                        f.get("workflows.diamond.ac.uk/posixuid")
+                            .is_some_and(|l| l == &posix_uid)
+                    })
+                {
                     Ok(Some(trigger.into()))
                 } else {
                     Err(TriggerError::ForbiddenAccess.into())
@@ -138,49 +148,77 @@ impl TriggerQuery {
         limit: Option<u32>,
     ) -> anyhow::Result<Connection<OpaqueCursor<String>, TriggerGQL, EmptyFields, EmptyFields>>
     {
-        let client = setup_client(ctx).await?;
         let posix_uid = get_posix_from_ctx(ctx).await?;
-        let api: Api<Trigger> = Api::all(client);
-
-        let continue_token = cursor
-            .as_ref()
-            .map(|cursor| {
-                OpaqueCursor::<String>::decode_cursor(cursor)
-                    .map(|c| c.0)
-                    .map_err(|_| anyhow::anyhow!("Cursor not valid"))
-            })
-            .transpose()?;
+        list_triggers(
+            ctx,
+            Some(format!("workflows.diamond.ac.uk/posixuid={}", posix_uid)),
+            cursor,
+            limit,
+        )
+        .await
+    }
+
+    /// Get the Triggers of every user across namespaces, for platform admins
+    #[graphql(guard = "AdminGuard")]
+    async fn all_triggers(
+        &self,
+        ctx: &Context<'_>,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<Connection<OpaqueCursor<String>, TriggerGQL, EmptyFields, EmptyFields>>
+    {
+        list_triggers(ctx, None, cursor, limit).await
+    }
+}
 
-        let mut lp = ListParams::default()
-            .labels(format!("workflows.diamond.ac.uk/posixuid={}", posix_uid).as_str())
-            .limit(limit.unwrap_or(10));
+/// Get a page of Triggers across namespaces, optionally only those matching a label selector
+async fn list_triggers(
+    ctx: &Context<'_>,
+    labels: Option<String>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+) -> anyhow::Result<Connection<OpaqueCursor<String>, TriggerGQL, EmptyFields, EmptyFields>> {
+    let client = setup_client(ctx).await?;
+    let api: Api<Trigger> = Api::all(client);
+
+    let continue_token = cursor
+        .as_ref()
+        .map(|cursor| {
+            OpaqueCursor::<String>::decode_cursor(cursor)
+                .map(|c| c.0)
+                .map_err(|_| CodedError::bad_input("Cursor not valid"))
+        })
+        .transpose()?;
 
-        if let Some(token) = &continue_token {
-            lp = lp.continue_token(token);
-        }
+    let mut lp = ListParams::default().limit(limit.unwrap_or(10));
+    if let Some(labels) = &labels {
+        lp = lp.labels(labels);
+    }
 
-        let triggers_response = api.list(&lp).await?;
+    if let Some(token) = &continue_token {
+        lp = lp.continue_token(token);
+    }
 
-        let triggers = triggers_response
-            .items
-            .into_iter()
-            .map(TriggerGQL::from)
-            .collect::<Vec<_>>();
+    let triggers_response = api.list(&lp).await?;
 
-        let next_continue_token = triggers_response.metadata.continue_;
+    let triggers = triggers_response
+        .items
+        .into_iter()
+        .map(TriggerGQL::from)
+        .collect::<Vec<_>>();
 
-        let mut connection =
-            Connection::new(continue_token.is_some(), next_continue_token.is_some());
+    let next_continue_token = triggers_response.metadata.continue_;
 
-        connection.edges.extend(triggers.into_iter().map(|trigger| {
-            Edge::new(
-                OpaqueCursor(next_continue_token.clone().unwrap_or_default()),
-                trigger,
-            )
-        }));
+    let mut connection = Connection::new(continue_token.is_some(), next_continue_token.is_some());
 
-        Ok(connection)
-    }
+    connection.edges.extend(triggers.into_iter().map(|trigger| {
+        Edge::new(
+            OpaqueCursor(next_continue_token.clone().unwrap_or_default()),
+            trigger,
+        )
+    }));
+
+    Ok(connection)
 }
 
 /// Mutations related to [`Trigger`]s
@@ -226,7 +264,11 @@ impl TriggerMutation {
 #[cfg(test)]
 mod tests {
     use crate::{
-        graphql::triggers::{TriggerMutation, TriggerQuery},
+        graphql::{
+            triggers::{TriggerMutation, TriggerQuery},
+            RoleArgs,
+        },
+        test_kubeconfig::TestKubeconfig,
         validate_token::ValidatedAuthToken,
         KubernetesApiUrl,
     };
@@ -269,42 +311,15 @@ mod tests {
     }
 
     struct TestContext {
-        _kubeconfig: tempfile::NamedTempFile,
+        _kubeconfig: TestKubeconfig,
         server: ServerGuard,
         schema: Schema<TriggerQuery, TriggerMutation, EmptySubscription>,
     }
 
     impl TestContext {
         async fn new() -> anyhow::Result<Self> {
-            let _ = rustls::crypto::ring::default_provider().install_default();
-
             let server = mockito::Server::new_async().await;
-
-            let kubeconfig = format!(
-                r#"
-apiVersion: v1
-kind: Config
-clusters:
-- cluster:
-    server: {}
-  name: test
-contexts:
-- context:
-    cluster: test
-    user: test
-  name: test
-current-context: test
-users:
-- name: test
-  user: {{}}
-"#,
-                server.url()
-            );
-
-            let file = tempfile::NamedTempFile::new()?;
-            std::fs::write(file.path(), kubeconfig)?;
-
-            std::env::set_var("KUBECONFIG", file.path());
+            let kubeconfig = TestKubeconfig::new(&server.url()).await?;
 
             let schema = Schema::build(TriggerQuery, TriggerMutation, EmptySubscription)
                 .data(KubernetesApiUrl(server.url().parse()?))
@@ -312,7 +327,7 @@ users:
                 .finish();
 
             Ok(Self {
-                _kubeconfig: file,
+                _kubeconfig: kubeconfig,
                 server,
                 schema,
             })
@@ -559,6 +574,82 @@ users:
         Ok(())
     }
 
+    #[tokio::test]
+    async fn admin_gets_triggers_of_every_user() -> anyhow::Result<()> {
+        let mut ctx = TestContext::new().await?;
+        let admin_token = encode(
+            &Header::default(),
+            &json!({"posix_uid": "1234", "groups": ["workflows_admin"]}),
+            &EncodingKey::from_secret(b"test-secret"),
+        )?;
+        let schema = Schema::build(TriggerQuery, TriggerMutation, EmptySubscription)
+            .data(KubernetesApiUrl(ctx.server.url().parse()?))
+            .data(RoleArgs {
+                role_claim: "groups".to_string(),
+                platform_admin_roles: vec!["workflows_admin".to_string()],
+                ..Default::default()
+            })
+            .data(ValidatedAuthToken::Valid(Authorization::bearer(
+                &admin_token,
+            )?))
+            .finish();
+        mock_get_trigger(
+            &mut ctx.server,
+            "example-trigger-mfvpj",
+            "unauthorised-trigger.json",
+        )
+        .await;
+        let list_mock = ctx
+            .server
+            .mock("GET", "/apis/workflows.diamond.ac.uk/v1alpha1/triggers")
+            .match_query(Matcher::Exact("&limit=10".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-many-triggers.json"))
+            .create_async()
+            .await;
+
+        let trigger = execute(
+            &schema,
+            r#"query { trigger(name: "example-trigger-mfvpj") { name } }"#,
+        )
+        .await?;
+        assert_eq!(
+            trigger,
+            json!({"trigger": {"name": "example-trigger-mfvpj"}})
+        );
+
+        let mut triggers = execute(
+            &schema,
+            r#"query { allTriggers { nodes { name beamline } } }"#,
+        )
+        .await?;
+        list_mock.assert_async().await;
+        triggers["triggers"] = triggers["allTriggers"].take();
+        triggers.as_object_mut().unwrap().remove("allTriggers");
+        assert_eq!(triggers, expected_triggers());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn all_triggers_is_forbidden_to_non_admins() -> anyhow::Result<()> {
+        let ctx = TestContext::new().await?;
+        let response = ctx
+            .schema
+            .execute(r#"query { allTriggers { nodes { name } } }"#)
+            .await;
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(
+            response.errors[0]
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .cloned(),
+            Some(async_graphql::Value::from("FORBIDDEN"))
+        );
+        Ok(())
+    }
+
     fn expected_triggers() -> Value {
         json!({
             "triggers": {
@@ -0,0 +1,73 @@
+use axum::http::Uri;
+use futures_util::{FutureExt, StreamExt};
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{
+    runtime::{
+        reflector::{self, Store},
+        watcher, WatchStreamExt,
+    },
+    Api, Client, Config,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+/// The label selecting the ConfigMaps maintained by sessionspaces
+pub(super) const SESSIONSPACES_LABEL_SELECTOR: &str = "app.kubernetes.io/managed-by=sessionspaces";
+
+/// The field selecting the ConfigMap describing the visit of each sessionspace
+pub(super) const SESSIONSPACES_FIELD_SELECTOR: &str = "metadata.name=sessionspaces";
+
+/// A cache of the sessionspaces ConfigMaps describing the members and instrument of each visit,
+/// kept up to date by watching the Kubernetes API
+#[derive(Clone)]
+pub struct VisitCache {
+    /// The sessionspaces ConfigMap of each visit
+    config_maps: Store<ConfigMap>,
+}
+
+impl std::fmt::Debug for VisitCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VisitCache")
+            .field("config_maps", &self.config_maps.len())
+            .finish()
+    }
+}
+
+impl VisitCache {
+    /// Creates the cache and spawns the reflector which populates it
+    pub async fn spawn(kubernetes_api_url: &Uri) -> anyhow::Result<Self> {
+        let mut config = Config::infer().await?;
+        config.cluster_url = kubernetes_api_url.clone();
+        let client = Client::try_from(config)?;
+
+        let writer = reflector::store::Writer::default();
+        let config_maps = writer.as_reader();
+        let watcher_config = watcher::Config::default()
+            .labels(SESSIONSPACES_LABEL_SELECTOR)
+            .fields(SESSIONSPACES_FIELD_SELECTOR);
+        let stream = reflector::reflector(
+            writer,
+            watcher(Api::<ConfigMap>::all(client), watcher_config).default_backoff(),
+        );
+        tokio::spawn(stream.for_each(|event| async move {
+            if let Err(err) = event {
+                warn!("Visit cache watch failed: {err}");
+            }
+        }));
+
+        Ok(Self { config_maps })
+    }
+
+    /// Whether the initial list of ConfigMaps has completed
+    pub(super) fn is_ready(&self) -> bool {
+        self.config_maps
+            .wait_until_ready()
+            .now_or_never()
+            .is_some_and(|ready| ready.is_ok())
+    }
+
+    /// Get the sessionspaces ConfigMap of every visit
+    pub(super) fn config_maps(&self) -> Vec<Arc<ConfigMap>> {
+        self.config_maps.state()
+    }
+}
@@ -1,26 +1,34 @@
 use super::{
+    artifacts::artifact_upload_prefix,
+    errors::CodedError,
     parameter_schema::{ArgumentSchema, ParameterSchemaError, Schema},
+    template_cache::TemplateCache,
+    triggers::setup_client,
     ui_schema::{UiSchema, UiSchemaError},
-    workflows::Workflow,
+    workflows::{ArgoList, Workflow},
     VisitInput, CLIENT,
 };
-use crate::{graphql::auth_guard::AuthGuard, validate_token::ValidatedAuthToken};
+use crate::{
+    graphql::auth_guard::AuthGuard, s3client::ArtifactUrlArgs, validate_token::ValidatedAuthToken,
+};
 use crate::{graphql::filters::WorkflowTemplatesFilter, ArgoServerUrl};
 use anyhow::anyhow;
-use argo_workflows_openapi::APIResult;
+use argo_workflows_openapi::{APIResult, IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplate};
 use async_graphql::{
     connection::{Connection, CursorType, Edge, EmptyFields, OpaqueCursor},
-    Context, Json, Object, SimpleObject,
+    Context, InputObject, Json, Object, SimpleObject,
 };
+use axum_extra::headers::{authorization::Bearer, Authorization};
 use kube::{
     api::{ApiResource, DynamicObject},
     core::GroupVersionKind,
-    Api, Client,
+    Api,
 };
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::{collections::HashMap, ops::Deref};
 use tracing::{debug, instrument};
+use url::Url;
 
 #[derive(Debug, thiserror::Error)]
 #[allow(clippy::missing_docs_in_private_items)]
@@ -108,15 +116,20 @@ impl WorkflowTemplate {
     }
 
     /// Information about where the template is obtained from
-    async fn template_source(
-        &self,
-    ) -> Result<Option<TemplateSource>, WorkflowTemplateParsingError> {
-        let instance = match self.metadata.labels.get("argocd.argoproj.io/instance") {
-            Some(val) => val,
-            None => return Err(WorkflowTemplateParsingError::MissingInstanceLabel),
-        };
+    async fn template_source(&self, ctx: &Context<'_>) -> anyhow::Result<Option<TemplateSource>> {
+        let instance = self
+            .metadata
+            .labels
+            .get("argocd.argoproj.io/instance")
+            .ok_or(WorkflowTemplateParsingError::MissingInstanceLabel)?;
+
+        if let Some(cache) = ready_template_cache(ctx) {
+            return Ok(cache
+                .application(instance)
+                .and_then(|application| application_source(&application)));
+        }
 
-        let client = Client::try_default().await.unwrap();
+        let client = setup_client(ctx).await?;
         let gvk = GroupVersionKind::gvk("argoproj.io", "v1alpha1", "application");
         let api = Api::<DynamicObject>::namespaced_with(
             client,
@@ -124,25 +137,54 @@ impl WorkflowTemplate {
             &ApiResource::from_gvk_with_plural(&gvk, "applications"),
         );
 
-        let obj = match api.get(instance).await {
-            Ok(obj) => obj,
-            Err(_) => return Ok(None),
-        };
-
-        let data = obj
-            .data
-            .get("spec")
-            .and_then(|s| s.get("source"))
-            .cloned()
-            .unwrap_or(Value::Null);
-
-        match serde_json::from_value(data) {
-            Ok(source) => Ok(Some(source)),
+        match api.get(instance).await {
+            Ok(application) => Ok(application_source(&application)),
             Err(_) => Ok(None),
         }
     }
 }
 
+/// Reads the source of a template from the ArgoCD Application which deployed it
+fn application_source(application: &DynamicObject) -> Option<TemplateSource> {
+    let data = application
+        .data
+        .get("spec")
+        .and_then(|s| s.get("source"))
+        .cloned()
+        .unwrap_or(Value::Null);
+    serde_json::from_value(data).ok()
+}
+
+/// The [`TemplateCache`], if one is configured and has been populated
+fn ready_template_cache<'a>(ctx: &Context<'a>) -> Option<&'a TemplateCache> {
+    ctx.data_opt::<TemplateCache>()
+        .filter(|cache| cache.is_ready())
+}
+
+/// Checks the requester may read cluster workflow templates, by listing them from the Argo Server
+async fn check_template_access(
+    server_url: &Url,
+    auth_token: Option<&Authorization<Bearer>>,
+) -> anyhow::Result<()> {
+    let mut url = server_url.clone();
+    url.path_segments_mut()
+        .unwrap()
+        .extend(["api", "v1", "cluster-workflow-templates"]);
+    url.query_pairs_mut()
+        .append_pair("listOptions.limit", "1")
+        .append_pair("fields", "metadata.resourceVersion");
+    debug!("Checking access to workflow templates at {url}");
+    let mut request = CLIENT.get(url);
+    if let Some(auth_token) = auth_token {
+        request = request.bearer_auth(auth_token.token());
+    }
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(CodedError::from_response(response).await.into());
+    }
+    Ok(())
+}
+
 /// Queries related to [`WorkflowTemplate`]s
 #[derive(Debug, Clone, Default)]
 pub struct WorkflowTemplatesQuery;
@@ -158,6 +200,15 @@ impl WorkflowTemplatesQuery {
     ) -> anyhow::Result<WorkflowTemplate> {
         let server_url = ctx.data_unchecked::<ArgoServerUrl>().deref();
         let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+        if let Some(cache) = ready_template_cache(ctx) {
+            check_template_access(server_url, auth_token).await?;
+            return cache
+                .template(&name)
+                .map(WorkflowTemplate::from)
+                .ok_or_else(|| {
+                    CodedError::not_found(format!("Workflow template {name} not found")).into()
+                });
+        }
         let mut url = server_url.clone();
         url.path_segments_mut()
             .unwrap()
@@ -168,15 +219,12 @@ impl WorkflowTemplatesQuery {
         } else {
             CLIENT.get(url)
         };
-        let workflow_templates =
-            request
-                .send()
-                .await?
-                .json::<APIResult<
-                    argo_workflows_openapi::IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplate,
-                >>()
-                .await?
-                .into_result()?;
+        let workflow_templates = request
+            .send()
+            .await?
+            .json::<APIResult<IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplate>>()
+            .await?
+            .into_result()?;
         Ok(workflow_templates.into())
     }
 
@@ -192,11 +240,42 @@ impl WorkflowTemplatesQuery {
     {
         let server_url = ctx.data_unchecked::<ArgoServerUrl>().deref();
         let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+        let limit = limit.unwrap_or(100);
+        if let Some(cache) = ready_template_cache(ctx) {
+            let cursor_index = match cursor {
+                Some(cursor) => {
+                    OpaqueCursor::<usize>::decode_cursor(&cursor)
+                        .map_err(|err| CodedError::bad_input(format!("Invalid Cursor: {err}")))?
+                        .0
+                }
+                None => 0,
+            };
+            check_template_access(server_url, auth_token).await?;
+            let mut templates = cache
+                .templates()
+                .into_iter()
+                .filter(|template| {
+                    filter
+                        .as_ref()
+                        .is_none_or(|filter| filter.matches(&template.metadata.labels))
+                })
+                .skip(cursor_index)
+                .peekable();
+            let mut connection = Connection::new(cursor_index > 0, false);
+            connection
+                .edges
+                .extend(templates.by_ref().take(limit as usize).enumerate().map(
+                    |(idx, template)| {
+                        Edge::new(OpaqueCursor(cursor_index + idx + 1), template.into())
+                    },
+                ));
+            connection.has_next_page = templates.peek().is_some();
+            return Ok(connection);
+        }
         let mut url = server_url.clone();
         url.path_segments_mut()
             .unwrap()
             .extend(["api", "v1", "cluster-workflow-templates"]);
-        let limit = limit.unwrap_or(100);
         url.query_pairs_mut()
             .append_pair("listOptions.limit", &limit.to_string());
 
@@ -205,7 +284,7 @@ impl WorkflowTemplatesQuery {
         }
         let cursor_index = if let Some(cursor) = cursor {
             let cursor_index = OpaqueCursor::<usize>::decode_cursor(&cursor)
-                .map_err(|err| anyhow!("Invalid Cursor: {err}"))?;
+                .map_err(|err| CodedError::bad_input(format!("Invalid Cursor: {err}")))?;
             url.query_pairs_mut()
                 .append_pair("listOptions.continue", &cursor_index.0.to_string());
             cursor_index.0
@@ -219,34 +298,20 @@ impl WorkflowTemplatesQuery {
             CLIENT.get(url)
         };
 
-        let api_result =
-            request
-                .send()
-                .await?
-                .json::<APIResult<
-                    argo_workflows_openapi::IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplateList,
-                >>()
-                .await?;
-
-        let workflow_templates_response = match api_result.into_result() {
-            Ok(res) => res,
-            Err(err) => {
-                if err.message.as_deref() == Some("Unauthorized") {
-                    return Err(err.into());
-                }
-                return Ok(Connection::new(false, false));
-            }
-        };
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(CodedError::from_response(response).await.into());
+        }
+        let workflow_templates_response = response
+            .json::<ArgoList<IoArgoprojWorkflowV1alpha1ClusterWorkflowTemplate>>()
+            .await?;
 
+        let mut connection =
+            Connection::new(cursor_index > 0, workflow_templates_response.has_more());
         let workflow_templates = workflow_templates_response
-            .items
-            .into_iter()
+            .into_items()
             .map(TryInto::try_into)
             .collect::<Result<Vec<_>, _>>()?;
-        let mut connection = Connection::new(
-            cursor_index > 0,
-            workflow_templates_response.metadata.continue_.is_some(),
-        );
         connection.edges.extend(
             workflow_templates
                 .into_iter()
@@ -257,6 +322,15 @@ impl WorkflowTemplatesQuery {
     }
 }
 
+/// An uploaded file supplied as an input artifact of a workflow
+#[derive(Debug, InputObject)]
+struct InputArtifact {
+    /// The name of the input artifact in the workflow template
+    name: String,
+    /// The key of the uploaded file, as returned when the upload was created
+    key: String,
+}
+
 /// Mutations related to [`WorkflowTemplate`]s
 #[derive(Debug, Clone, Default)]
 pub struct WorkflowTemplatesMutation;
@@ -271,7 +345,18 @@ impl WorkflowTemplatesMutation {
         name: String,
         visit: VisitInput,
         parameters: Json<HashMap<String, Value>>,
+        #[graphql(default)] artifacts: Vec<InputArtifact>,
     ) -> anyhow::Result<Workflow> {
+        if !artifacts.is_empty() {
+            return submit_workflow_template_with_artifacts(
+                ctx,
+                name,
+                visit,
+                parameters.0,
+                artifacts,
+            )
+            .await;
+        }
         let server_url = ctx.data_unchecked::<ArgoServerUrl>().deref();
         let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
         let mut url = server_url.clone();
@@ -312,9 +397,11 @@ impl WorkflowTemplatesMutation {
                 ),
             },
         );
-        let workflow = request
-            .send()
-            .await?
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(CodedError::from_response(response).await.into());
+        }
+        let workflow = response
             .json::<APIResult<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
             .await?
             .into_result()?;
@@ -370,9 +457,11 @@ impl WorkflowTemplatesMutation {
             },
         );
 
-        let workflow = request
-            .send()
-            .await?
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(CodedError::from_response(response).await.into());
+        }
+        let workflow = response
             .json::<APIResult<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
             .await?
             .into_result()?;
@@ -380,17 +469,112 @@ impl WorkflowTemplatesMutation {
     }
 }
 
+/// Submit a workflow template as a Workflow referencing it, with uploaded files as input artifacts
+///
+/// The submit endpoint of the Argo Server only accepts parameters, so the Workflow is created
+/// directly, labelled with its template as the submit endpoint would. The artifacts are referenced
+/// by key alone, resolving against the default artifact repository, and must have been uploaded
+/// to the visit.
+async fn submit_workflow_template_with_artifacts(
+    ctx: &Context<'_>,
+    name: String,
+    visit: VisitInput,
+    parameters: HashMap<String, Value>,
+    artifacts: Vec<InputArtifact>,
+) -> anyhow::Result<Workflow> {
+    let server_url = ctx.data_unchecked::<ArgoServerUrl>().deref();
+    let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+    let url_args = ctx
+        .data_opt::<ArtifactUrlArgs>()
+        .cloned()
+        .unwrap_or_default();
+    let upload_prefix = artifact_upload_prefix(&url_args, &visit);
+    if let Some(artifact) = artifacts
+        .iter()
+        .find(|artifact| !artifact.key.starts_with(&upload_prefix) || artifact.key.contains(".."))
+    {
+        return Err(CodedError::bad_input(format!(
+            "Artifact {} was not uploaded to visit {visit}",
+            artifact.name
+        ))
+        .into());
+    }
+    let parameters = parameters
+        .into_iter()
+        .filter_map(|(name, value)| {
+            to_argo_parameter_value(value)
+                .map(|value| value.map(|value| json!({"name": name, "value": value})))
+                .transpose()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let artifacts = artifacts
+        .into_iter()
+        .map(|artifact| json!({"name": artifact.name, "s3": {"key": artifact.key}}))
+        .collect::<Vec<_>>();
+    let workflow: argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow =
+        serde_json::from_value(json!({
+            "metadata": {
+                "generateName": format!("{name}-"),
+                "labels": {
+                    "workflows.argoproj.io/cluster-workflow-template": name,
+                },
+            },
+            "spec": {
+                "workflowTemplateRef": {
+                    "name": name,
+                    "clusterScope": true,
+                },
+                "arguments": {
+                    "parameters": parameters,
+                    "artifacts": artifacts,
+                },
+            },
+        }))?;
+
+    let namespace = visit.to_string();
+    let mut url = server_url.clone();
+    url.path_segments_mut()
+        .unwrap()
+        .extend(["api", "v1", "workflows", &namespace]);
+    debug!("Submitting workflow template with artifacts at {url}");
+    let request = if let Some(auth_token) = auth_token {
+        CLIENT.post(url).bearer_auth(auth_token.token())
+    } else {
+        CLIENT.post(url)
+    }
+    .json(
+        &argo_workflows_openapi::IoArgoprojWorkflowV1alpha1WorkflowCreateRequest {
+            namespace: Some(namespace),
+            workflow: Some(workflow),
+            ..Default::default()
+        },
+    );
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(CodedError::from_response(response).await.into());
+    }
+    let workflow = response
+        .json::<APIResult<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
+        .await?
+        .into_result()?;
+    Ok(Workflow::new(workflow, visit.into()))
+}
+
 /// Convert a paramter into the format expected by the Argo Workflows API
 fn to_argo_parameter(name: String, value: Value) -> Result<Option<String>, serde_json::Error> {
-    Ok(match value {
+    Ok(to_argo_parameter_value(value)?.map(|parameter| format!("{name}={parameter}")))
+}
+
+/// Convert a parameter value into the string expected by the Argo Workflows API
+fn to_argo_parameter_value(value: Value) -> Result<Option<String>, serde_json::Error> {
+    match value {
         Value::Null => Ok(None),
         Value::Bool(bool) => Ok(Some(bool.to_string())),
         Value::Number(number) => Ok(Some(number.to_string())),
         Value::String(string) => Ok(Some(string)),
         Value::Array(vec) => serde_json::to_string(&vec).map(Some),
         Value::Object(map) => serde_json::to_string(&map).map(Some),
-    }?
-    .map(|parameter| format!("{name}={parameter}")))
+    }
 }
 
 #[cfg(test)]
@@ -715,4 +899,319 @@ mod tests {
         assert_eq!(expected, actual);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn submit_workflow_template_with_artifacts_mutation() -> anyhow::Result<()> {
+        use super::WorkflowTemplatesMutation;
+        use mockito::Matcher;
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("submit-workflow.json");
+        let submit_endpoint = server
+            .mock("POST", "/api/v1/workflows/mg36964-1")
+            .match_body(Matcher::PartialJson(json!({
+                "namespace": "mg36964-1",
+                "workflow": {
+                    "metadata": {
+                        "generateName": "conformer-",
+                        "labels": {
+                            "workflows.argoproj.io/cluster-workflow-template": "conformer"
+                        }
+                    },
+                    "spec": {
+                        "workflowTemplateRef": {
+                            "name": "conformer",
+                            "clusterScope": true
+                        },
+                        "arguments": {
+                            "parameters": [{"name": "steps", "value": "10"}],
+                            "artifacts": [{
+                                "name": "model",
+                                "s3": {"key": "uploads/mg36964-1/1234/model.pdb"}
+                            }]
+                        }
+                    }
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = url::Url::parse(&server.url())?;
+        let schema = Schema::build(
+            WorkflowTemplatesQuery,
+            WorkflowTemplatesMutation,
+            EmptySubscription,
+        )
+        .data(crate::ArgoServerUrl(argo_server_url))
+        .data(test_token())
+        .finish();
+
+        let query = r#"
+            mutation {
+                submitWorkflowTemplate(
+                    name: "conformer",
+                    visit: { proposalCode: "mg", proposalNumber: 36964, number: 1 },
+                    parameters: { steps: 10 },
+                    artifacts: [{ name: "model", key: "uploads/mg36964-1/1234/model.pdb" }]
+                ) {
+                    name
+                }
+            }
+        "#;
+        let response = schema.execute(query).await.into_result().unwrap();
+
+        submit_endpoint.assert_async().await;
+        let expected = json!({
+            "submitWorkflowTemplate": {
+                "name": "test-workflow-abcde"
+            }
+        });
+        assert_eq!(expected, response.data.into_json()?);
+
+        let query = r#"
+            mutation {
+                submitWorkflowTemplate(
+                    name: "conformer",
+                    visit: { proposalCode: "mg", proposalNumber: 36964, number: 1 },
+                    parameters: {},
+                    artifacts: [{ name: "model", key: "uploads/mg36964-2/1234/model.pdb" }]
+                ) {
+                    name
+                }
+            }
+        "#;
+        let response = schema.execute(query).await;
+        assert_eq!(
+            response.errors[0].message,
+            "Artifact model was not uploaded to visit mg36964-1"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(
+        403,
+        r#"{"code":7,"message":"workflows.argoproj.io is forbidden"}"#,
+        "FORBIDDEN"
+    )]
+    #[case(
+        409,
+        r#"{"code":6,"message":"workflows.argoproj.io already exists"}"#,
+        "CONFLICT"
+    )]
+    async fn rejected_submit_workflow_template_with_artifacts_is_coded(
+        #[case] status: usize,
+        #[case] body: &str,
+        #[case] expected_code: &str,
+    ) -> anyhow::Result<()> {
+        use super::WorkflowTemplatesMutation;
+        use crate::graphql::errors::UpstreamErrorCodes;
+
+        let mut server = mockito::Server::new_async().await;
+        let submit_endpoint = server
+            .mock("POST", "/api/v1/workflows/mg36964-1")
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let argo_server_url = url::Url::parse(&server.url())?;
+        let schema = Schema::build(
+            WorkflowTemplatesQuery,
+            WorkflowTemplatesMutation,
+            EmptySubscription,
+        )
+        .extension(UpstreamErrorCodes)
+        .data(crate::ArgoServerUrl(argo_server_url))
+        .data(test_token())
+        .finish();
+
+        let query = r#"
+            mutation {
+                submitWorkflowTemplate(
+                    name: "conformer",
+                    visit: { proposalCode: "mg", proposalNumber: 36964, number: 1 },
+                    parameters: {},
+                    artifacts: [{ name: "model", key: "uploads/mg36964-1/1234/model.pdb" }]
+                ) {
+                    name
+                }
+            }
+        "#;
+        let response = schema.execute(query).await;
+
+        submit_endpoint.assert_async().await;
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(
+            response.errors[0]
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .cloned(),
+            Some(async_graphql::Value::from(expected_code))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn workflow_templates_are_served_from_cache() -> anyhow::Result<()> {
+        use super::TemplateCache;
+        use crate::test_kubeconfig::TestKubeconfig;
+        use mockito::Matcher;
+
+        let mut server = mockito::Server::new_async().await;
+        let _kubeconfig = TestKubeconfig::new(&server.url()).await?;
+
+        let mut response_file_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-template.json");
+        let numpy_template: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(response_file_path)?)?;
+        let mx_template = json!({
+            "metadata": {
+                "name": "mx-template",
+                "labels": {
+                    "argocd.argoproj.io/instance": "mx-manifests",
+                    "workflows.diamond.ac.uk/science-group-mx": "true"
+                }
+            },
+            "spec": {}
+        });
+        server
+            .mock("GET", "/apis/argoproj.io/v1alpha1/clusterworkflowtemplates")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "apiVersion": "argoproj.io/v1alpha1",
+                    "kind": "ClusterWorkflowTemplateList",
+                    "metadata": {"resourceVersion": "1"},
+                    "items": [numpy_template, mx_template]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+            .mock(
+                "GET",
+                "/apis/argoproj.io/v1alpha1/namespaces/argocd/applications",
+            )
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "apiVersion": "argoproj.io/v1alpha1",
+                    "kind": "ApplicationList",
+                    "metadata": {"resourceVersion": "1"},
+                    "items": [{
+                        "metadata": {"name": "mx-manifests", "namespace": "argocd"},
+                        "spec": {
+                            "source": {
+                                "repoURL": "https://github.com/DiamondLightSource/mx-workflows",
+                                "path": "templates",
+                                "targetRevision": "main"
+                            }
+                        }
+                    }]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        for path in [
+            "/apis/argoproj.io/v1alpha1/clusterworkflowtemplates",
+            "/apis/argoproj.io/v1alpha1/namespaces/argocd/applications",
+        ] {
+            server
+                .mock("GET", path)
+                .match_query(Matcher::UrlEncoded("watch".to_string(), "true".to_string()))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .create_async()
+                .await;
+        }
+        let access_endpoint = server
+            .mock("GET", "/api/v1/cluster-workflow-templates")
+            .match_query(Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                "1".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"metadata": {}, "items": []}).to_string())
+            .create_async()
+            .await;
+        let list_endpoint = server
+            .mock("GET", "/api/v1/cluster-workflow-templates")
+            .match_query(Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                "100".to_string(),
+            ))
+            .expect(0)
+            .create_async()
+            .await;
+
+        let template_cache = TemplateCache::spawn(&server.url().parse()?).await?;
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while !template_cache.is_ready() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await?;
+
+        let argo_server_url = url::Url::parse(&server.url())?;
+        let schema = Schema::build(WorkflowTemplatesQuery, EmptyMutation, EmptySubscription)
+            .data(crate::ArgoServerUrl(argo_server_url))
+            .data(template_cache)
+            .data(test_token())
+            .finish();
+        let query = r#"
+            query {
+                workflowTemplates(filter: { scienceGroup: MX }) {
+                    nodes {
+                        name
+                        templateSource {
+                            repositoryUrl
+                            path
+                            targetRevision
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                    }
+                }
+            }
+        "#;
+        let response = schema.execute(query).await.into_result().unwrap();
+        access_endpoint.assert_async().await;
+        list_endpoint.assert_async().await;
+
+        let expected = json!({
+            "workflowTemplates": {
+                "nodes": [{
+                    "name": "mx-template",
+                    "templateSource": {
+                        "repositoryUrl": "https://github.com/DiamondLightSource/mx-workflows",
+                        "path": "templates",
+                        "targetRevision": "main"
+                    }
+                }],
+                "pageInfo": {
+                    "hasNextPage": false
+                }
+            }
+        });
+        assert_eq!(response.data.into_json()?, expected);
+        Ok(())
+    }
 }
@@ -1,12 +1,34 @@
 use super::{Visit, VisitInput, CLIENT};
 use crate::{
-    graphql::{auth_guard::AuthGuard, filters::WorkflowFilter},
-    validate_token::ValidatedAuthToken,
-    ArgoServerUrl, S3Bucket,
+    graphql::{
+        artifacts::artifact_route_url,
+        auth_guard::AuthGuard,
+        errors::CodedError,
+        filters::{CreatorId, WorkflowFilter, WorkflowSortOrder},
+        loaders::{loaded, ArtifactMetadataDataLoader, WorkflowDataLoader, WorkflowKey},
+        pods::{
+            container_statuses, get_pod, list_pod_events, pod_conditions, ContainerStatus,
+            PodCondition, PodEvent,
+        },
+        roles::{privileged_token, AdminGuard, StaffGuard},
+        subscription::{get_auth_token, LogEntry, LogResponse},
+        triggers::setup_client,
+        visit_cache::{VisitCache, SESSIONSPACES_FIELD_SELECTOR, SESSIONSPACES_LABEL_SELECTOR},
+    },
+    s3client::{head_artifact, ArtifactMetadata, ArtifactUrlArgs, ArtifactUrlMode},
+    validate_token::{TokenHolder, TokenValidator, ValidatedAuthToken},
+    ArgoServerUrl, ArtifactMetadataCache, ArtifactPreviewArgs, S3Bucket,
 };
 use argo_workflows_openapi::{
-    APIResult, IoArgoprojWorkflowV1alpha1Artifact, IoArgoprojWorkflowV1alpha1NodeStatus,
-    IoArgoprojWorkflowV1alpha1Workflow, IoArgoprojWorkflowV1alpha1WorkflowStatus,
+    APIResult, IoArgoprojWorkflowV1alpha1Artifact, IoArgoprojWorkflowV1alpha1Inputs,
+    IoArgoprojWorkflowV1alpha1NodeStatus, IoArgoprojWorkflowV1alpha1Outputs,
+    IoArgoprojWorkflowV1alpha1Parameter, IoArgoprojWorkflowV1alpha1Workflow,
+    IoArgoprojWorkflowV1alpha1WorkflowResubmitRequest,
+    IoArgoprojWorkflowV1alpha1WorkflowResumeRequest,
+    IoArgoprojWorkflowV1alpha1WorkflowRetryRequest, IoArgoprojWorkflowV1alpha1WorkflowStatus,
+    IoArgoprojWorkflowV1alpha1WorkflowStopRequest,
+    IoArgoprojWorkflowV1alpha1WorkflowSuspendRequest,
+    IoArgoprojWorkflowV1alpha1WorkflowTerminateRequest,
 };
 use async_graphql::{
     connection::{Connection, CursorType, Edge, EmptyFields, OpaqueCursor},
@@ -14,9 +36,27 @@ use async_graphql::{
 };
 use aws_sdk_s3::presigning::PresigningConfig;
 use axum_extra::headers::{authorization::Bearer, Authorization};
-use chrono::{DateTime, Utc};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use chrono::{DateTime, TimeDelta, Utc};
+use futures_util::{future::join_all, StreamExt};
+use image::ImageFormat;
+use jsonwebtoken::dangerous::insecure_decode;
+use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+use kube::{api::ListParams, Api};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use serde_json::{from_str, Value};
-use std::{collections::HashMap, ops::Deref, path::Path};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    future::Future,
+    io::Cursor,
+    ops::Deref,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
+use tokio::sync::OnceCell;
 use tracing::{debug, instrument};
 use url::Url;
 
@@ -48,6 +88,8 @@ pub(super) enum WorkflowParsingError {
     MissingS3Bucket,
     #[error("invalid presigned s3 url")]
     InvalidPresignedS3Url,
+    #[error("artifact proxy url was expected but was not a valid base url")]
+    MissingArtifactProxyUrl,
     #[error("No authorisation token was provided")]
     MissingAuthToken,
 }
@@ -70,11 +112,57 @@ impl Workflow {
             .clone()
             .expect("Workflow missing name");
         let uid = manifest.metadata.uid.clone().expect("Workflow missing uid");
+        let pod_name_format = manifest
+            .metadata
+            .annotations
+            .get(POD_NAME_FORMAT_ANNOTATION)
+            .cloned();
         Workflow {
             manifest,
-            metadata: Metadata { name, visit, uid },
+            metadata: Metadata {
+                name,
+                visit,
+                uid,
+                archived: false,
+                pod_name_format,
+                privileged_token: None,
+            },
         }
     }
+
+    /// Create [`Workflow`] from an [`IoArgoprojWorkflowV1alpha1Workflow`] retrieved from the workflow archive
+    pub fn from_archive(manifest: IoArgoprojWorkflowV1alpha1Workflow, visit: Visit) -> Workflow {
+        let mut workflow = Workflow::new(manifest, visit);
+        workflow.metadata.archived = true;
+        workflow
+    }
+
+    /// Fetch the details of the workflow, such as its tasks and their logs, with the token it was
+    /// listed with by a staff or admin view, rather than that of the requester
+    fn with_privileged_token(mut self, token: &Authorization<Bearer>) -> Workflow {
+        self.metadata.privileged_token = Some(token.clone());
+        self
+    }
+
+    /// The time at which the workflow was created
+    fn creation_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.manifest
+            .metadata
+            .creation_timestamp
+            .as_ref()
+            .map(|time| **time)
+    }
+
+    /// The time the workflow has run for, up to now if it has not yet finished
+    fn duration(&self) -> Option<TimeDelta> {
+        let status = self.manifest.status.as_ref()?;
+        let started_at = **status.started_at.as_ref()?;
+        let finished_at = status
+            .finished_at
+            .as_ref()
+            .map_or_else(Utc::now, |time| **time);
+        Some(finished_at - started_at)
+    }
 }
 
 #[Object]
@@ -132,6 +220,42 @@ impl Workflow {
     async fn creator(&self) -> WorkflowCreator {
         WorkflowCreator::from_argo_workflow_labels(&self.manifest.metadata.labels)
     }
+
+    /// Whether the workflow was retrieved from the workflow archive
+    async fn archived(&self) -> bool {
+        self.metadata.archived
+    }
+
+    /// The outputs of the workflow, exported from its tasks
+    async fn outputs(&self) -> Option<Outputs<'_>> {
+        self.manifest
+            .status
+            .as_ref()
+            .and_then(|status| status.outputs.as_ref())
+            .map(Outputs)
+    }
+
+    /// The download URL of a zip archive of the artifacts of all, or only the given, tasks
+    ///
+    /// Null unless the externally reachable URL of the proxy is configured.
+    async fn artifact_bundle_url(
+        &self,
+        ctx: &Context<'_>,
+        tasks: Option<Vec<String>>,
+    ) -> Option<Url> {
+        let proxy_url = ctx
+            .data_opt::<ArtifactUrlArgs>()?
+            .artifact_proxy_url
+            .as_ref()?;
+        let mut url = artifact_route_url(
+            proxy_url,
+            &[&self.metadata.visit.to_string(), &self.metadata.name],
+        )?;
+        if let Some(tasks) = tasks {
+            url.query_pairs_mut().append_pair("tasks", &tasks.join(","));
+        }
+        Some(url)
+    }
 }
 
 /// Metadata of a workflow
@@ -143,6 +267,12 @@ pub(super) struct Metadata {
     visit: Visit,
     /// Unique ID identifying a workflow
     uid: String,
+    /// Whether the workflow was retrieved from the workflow archive
+    archived: bool,
+    /// The format used to name the pods of the workflow, if not the default
+    pod_name_format: Option<String>,
+    /// The token with which a staff or admin view listed the workflow, if it was listed by one
+    privileged_token: Option<Authorization<Bearer>>,
 }
 
 /// The status of a workflow
@@ -234,15 +364,26 @@ impl WorkflowRunningStatus<'_> {
         self.manifest.message.as_deref()
     }
 
+    /// The number of completed and total pods of the workflow
+    async fn progress(&self) -> Option<Progress> {
+        self.manifest.progress.as_deref().and_then(Progress::parse)
+    }
+
+    /// The seconds of each resource, such as cpu, memory or nvidia.com/gpu, used by the workflow
+    async fn resources_duration(&self) -> &HashMap<String, i64> {
+        &self.manifest.resources_duration
+    }
+
+    /// The estimated time, in seconds, the workflow takes to run, based on previous runs
+    async fn estimated_duration(&self) -> Option<i64> {
+        self.manifest.estimated_duration
+    }
+
     /// Tasks created by the workflow
+    #[graphql(complexity = "TASKS_COMPLEXITY_FACTOR * child_complexity")]
     async fn tasks(&self, ctx: &Context<'_>) -> anyhow::Result<Vec<Task>> {
-        let url = ctx.data_unchecked::<ArgoServerUrl>().deref().to_owned();
-        let token = ctx
-            .data_unchecked::<ValidatedAuthToken>()
-            .as_token()
-            .to_owned();
-        let nodes = fetch_missing_task_info(url, token, self.manifest, self.metadata).await?;
-        Ok(TaskMap(nodes).into_tasks())
+        let nodes = fetch_missing_task_info(ctx, self.manifest, self.metadata).await?;
+        Ok(TaskMap::new(nodes, self.metadata).into_tasks())
     }
 }
 
@@ -303,12 +444,98 @@ impl WorkflowCompleteStatus<'_> {
         self.manifest.message.as_deref()
     }
 
+    /// The number of completed and total pods of the workflow
+    async fn progress(&self) -> Option<Progress> {
+        self.manifest.progress.as_deref().and_then(Progress::parse)
+    }
+
+    /// The seconds of each resource, such as cpu, memory or nvidia.com/gpu, used by the workflow
+    async fn resources_duration(&self) -> &HashMap<String, i64> {
+        &self.manifest.resources_duration
+    }
+
+    /// The estimated time, in seconds, the workflow takes to run, based on previous runs
+    async fn estimated_duration(&self) -> Option<i64> {
+        self.manifest.estimated_duration
+    }
+
     /// Tasks created by the workflow
+    #[graphql(complexity = "TASKS_COMPLEXITY_FACTOR * child_complexity")]
     async fn tasks(&self, ctx: &Context<'_>) -> anyhow::Result<Vec<Task>> {
-        let url = ctx.data_unchecked::<ArgoServerUrl>().deref().to_owned();
-        let token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
-        let nodes = fetch_missing_task_info(url, token, self.manifest, self.metadata).await?;
-        Ok(TaskMap(nodes).into_tasks())
+        let nodes = fetch_missing_task_info(ctx, self.manifest, self.metadata).await?;
+        Ok(TaskMap::new(nodes, self.metadata).into_tasks())
+    }
+}
+
+/// A named parameter passed into or out of a [`Task`] or [`Workflow`]
+#[derive(Debug, SimpleObject)]
+struct Parameter<'a> {
+    /// The name of the parameter
+    name: &'a str,
+    /// The value of the parameter, if one has been resolved
+    value: Option<&'a str>,
+}
+
+impl<'a> From<&'a IoArgoprojWorkflowV1alpha1Parameter> for Parameter<'a> {
+    fn from(parameter: &'a IoArgoprojWorkflowV1alpha1Parameter) -> Self {
+        Self {
+            name: &parameter.name,
+            value: parameter.value.as_deref(),
+        }
+    }
+}
+
+/// The inputs a [`Task`] was run with
+#[derive(Debug)]
+struct Inputs<'a>(&'a IoArgoprojWorkflowV1alpha1Inputs);
+
+#[Object]
+impl Inputs<'_> {
+    /// Input parameters
+    async fn parameters(&self) -> Vec<Parameter<'_>> {
+        self.0.parameters.iter().map(Parameter::from).collect()
+    }
+}
+
+/// The outputs produced by a [`Task`] or [`Workflow`]
+#[derive(Debug)]
+struct Outputs<'a>(&'a IoArgoprojWorkflowV1alpha1Outputs);
+
+#[Object]
+impl Outputs<'_> {
+    /// Output parameters
+    async fn parameters(&self) -> Vec<Parameter<'_>> {
+        self.0.parameters.iter().map(Parameter::from).collect()
+    }
+
+    /// The exit code of the main container
+    async fn exit_code(&self) -> Option<i32> {
+        self.0.exit_code.as_deref()?.parse().ok()
+    }
+
+    /// The standard output of a script template, or the exit code of a container template
+    async fn result(&self) -> Option<&str> {
+        self.0.result.as_deref()
+    }
+}
+
+/// The number of completed and total pods of a workflow or task
+#[derive(Debug, SimpleObject, PartialEq, Eq)]
+struct Progress {
+    /// The number of pods which have completed
+    completed: u32,
+    /// The total number of pods
+    total: u32,
+}
+
+impl Progress {
+    /// Parse the progress from the `completed/total` format recorded by Argo Workflows
+    fn parse(progress: &str) -> Option<Self> {
+        let (completed, total) = progress.split_once('/')?;
+        Some(Self {
+            completed: completed.parse().ok()?,
+            total: total.parse().ok()?,
+        })
     }
 }
 
@@ -342,35 +569,75 @@ impl TryFrom<String> for TaskStatus {
 }
 
 /// An output produced by a [`Task`] within a [`Workflow`]
-#[allow(clippy::missing_docs_in_private_items)]
 #[derive(Debug)]
-struct Artifact<'a>(&'a IoArgoprojWorkflowV1alpha1Artifact);
+struct Artifact<'a> {
+    /// Manifest of the artifact
+    manifest: &'a IoArgoprojWorkflowV1alpha1Artifact,
+    /// The task which produced the artifact
+    task: &'a Task,
+}
+
+impl Artifact<'_> {
+    /// Get the metadata of the artifact object, from the cache if one is available
+    async fn object_metadata(&self, ctx: &Context<'_>) -> anyhow::Result<Arc<ArtifactMetadata>> {
+        let s3_client = ctx
+            .data::<aws_sdk_s3::Client>()
+            .map_err(|_| WorkflowParsingError::MissingS3Client)?;
+        let s3_bucket = ctx
+            .data::<S3Bucket>()
+            .map_err(|_| WorkflowParsingError::MissingS3Bucket)?;
+        let key = artifact_key(self.manifest)?;
+        if let Some(loader) = ctx.data_opt::<ArtifactMetadataDataLoader>() {
+            return loaded(loader.load_one(key.to_string()).await);
+        }
+        match ctx.data_opt::<ArtifactMetadataCache>() {
+            Some(cache) => cache.get(s3_client, s3_bucket, key).await,
+            None => head_artifact(s3_client, s3_bucket, key).await,
+        }
+    }
+}
 
 #[Object]
 impl Artifact<'_> {
     /// The file name of the artifact
     async fn name(&self) -> Result<&str, WorkflowParsingError> {
-        artifact_filename(self.0)
+        artifact_filename(self.manifest)
     }
 
     /// The download URL for the artifact
+    #[graphql(complexity = "S3_REQUEST_COMPLEXITY")]
     async fn url(&self, ctx: &Context<'_>) -> Result<Url, WorkflowParsingError> {
+        let url_args = ctx
+            .data_opt::<ArtifactUrlArgs>()
+            .cloned()
+            .unwrap_or_default();
+        if url_args.artifact_url_mode == ArtifactUrlMode::Proxied {
+            return url_args
+                .artifact_proxy_url
+                .and_then(|proxy_url| {
+                    artifact_route_url(
+                        &proxy_url,
+                        &[
+                            &self.task.visit,
+                            &self.task.workflow_name,
+                            &self.task.node_status.id,
+                            &self.manifest.name,
+                        ],
+                    )
+                })
+                .ok_or(WorkflowParsingError::MissingArtifactProxyUrl);
+        }
         let s3_client = ctx
             .data::<aws_sdk_s3::Client>()
             .map_err(|_| WorkflowParsingError::MissingS3Client)?;
         let s3_bucket = ctx
             .data::<S3Bucket>()
             .map_err(|_| WorkflowParsingError::MissingS3Bucket)?;
-        let key = self
-            .0
-            .s3
-            .as_ref()
-            .ok_or(WorkflowParsingError::UnrecognisedArtifactStore)?
-            .key
-            .as_ref()
-            .ok_or(WorkflowParsingError::MissingArtifactKey)?;
+        let key = artifact_key(self.manifest)?;
         let presigning_config = PresigningConfig::builder()
-            .expires_in(std::time::Duration::from_secs(3600))
+            .expires_in(std::time::Duration::from_secs(
+                url_args.artifact_presign_expiry,
+            ))
             .build()
             .unwrap();
         s3_client
@@ -387,38 +654,213 @@ impl Artifact<'_> {
 
     /// The MIME type of the artifact data
     async fn mime_type(&self) -> Result<&str, WorkflowParsingError> {
-        let filename = artifact_filename(self.0)?;
-        Ok(mime_guess::from_path(filename)
-            .first_raw()
-            .unwrap_or("application/octet-stream"))
+        artifact_mime_type(self.manifest)
+    }
+
+    /// The size of the artifact in bytes
+    #[graphql(complexity = "S3_REQUEST_COMPLEXITY")]
+    async fn size(&self, ctx: &Context<'_>) -> anyhow::Result<Option<i64>> {
+        Ok(self.object_metadata(ctx).await?.size)
+    }
+
+    /// The time at which the artifact was last written
+    #[graphql(complexity = "S3_REQUEST_COMPLEXITY")]
+    async fn last_modified(&self, ctx: &Context<'_>) -> anyhow::Result<Option<DateTime<Utc>>> {
+        Ok(self.object_metadata(ctx).await?.last_modified)
+    }
+
+    /// The entity tag of the artifact, which changes whenever its content does
+    #[graphql(complexity = "S3_REQUEST_COMPLEXITY")]
+    async fn etag(&self, ctx: &Context<'_>) -> anyhow::Result<Option<String>> {
+        Ok(self.object_metadata(ctx).await?.etag.clone())
     }
+
+    /// User defined metadata attached to the artifact
+    #[graphql(complexity = "S3_REQUEST_COMPLEXITY")]
+    async fn metadata(&self, ctx: &Context<'_>) -> anyhow::Result<HashMap<String, String>> {
+        Ok(self.object_metadata(ctx).await?.metadata.clone())
+    }
+
+    /// An inline preview of the artifact, if it is text or a PNG or JPEG image
+    #[graphql(complexity = "DOWNLOAD_COMPLEXITY + child_complexity")]
+    async fn preview(&self, ctx: &Context<'_>) -> anyhow::Result<Option<ArtifactPreview>> {
+        let s3_client = ctx
+            .data::<aws_sdk_s3::Client>()
+            .map_err(|_| WorkflowParsingError::MissingS3Client)?;
+        let s3_bucket = ctx
+            .data::<S3Bucket>()
+            .map_err(|_| WorkflowParsingError::MissingS3Bucket)?;
+        let preview_args = ctx
+            .data_opt::<ArtifactPreviewArgs>()
+            .copied()
+            .unwrap_or_default();
+        let key = artifact_key(self.manifest)?;
+        let mime_type = artifact_mime_type(self.manifest)?;
+
+        if is_text_mime_type(mime_type) {
+            let max_bytes = preview_args.artifact_preview_max_bytes;
+            let object = s3_client
+                .get_object()
+                .bucket(s3_bucket.clone())
+                .key(key)
+                .range(format!("bytes=0-{max_bytes}"))
+                .send()
+                .await?;
+            let mut content = object.body.collect().await?.to_vec();
+            let truncated = content.len() > max_bytes;
+            content.truncate(max_bytes);
+            Ok(Some(ArtifactPreview::Text(TextArtifactPreview {
+                content: String::from_utf8_lossy(&content).into_owned(),
+                truncated,
+            })))
+        } else if matches!(mime_type, "image/png" | "image/jpeg") {
+            let object = s3_client
+                .get_object()
+                .bucket(s3_bucket.clone())
+                .key(key)
+                .send()
+                .await?;
+            if object
+                .content_length
+                .is_some_and(|length| length > MAX_THUMBNAIL_SOURCE_BYTES)
+            {
+                return Ok(None);
+            }
+            let image = object.body.collect().await?.to_vec();
+            let size = preview_args.artifact_thumbnail_size;
+            let thumbnail = tokio::task::spawn_blocking(move || {
+                let thumbnail = image::load_from_memory(&image)?.thumbnail(size, size);
+                let mut data = Cursor::new(Vec::new());
+                thumbnail.write_to(&mut data, ImageFormat::Png)?;
+                Ok::<_, image::ImageError>(ImageArtifactPreview {
+                    mime_type: "image/png",
+                    data: BASE64_STANDARD.encode(data.into_inner()),
+                    width: thumbnail.width(),
+                    height: thumbnail.height(),
+                })
+            })
+            .await??;
+            Ok(Some(ArtifactPreview::Image(thumbnail)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The largest image, in bytes, from which a thumbnail will be generated
+const MAX_THUMBNAIL_SOURCE_BYTES: i64 = 32 * 1024 * 1024;
+
+/// An inline preview of an [`Artifact`]
+#[derive(Debug, Union)]
+#[allow(clippy::missing_docs_in_private_items)]
+enum ArtifactPreview {
+    Text(TextArtifactPreview),
+    Image(ImageArtifactPreview),
+}
+
+/// The leading content of a text artifact
+#[derive(Debug, SimpleObject)]
+struct TextArtifactPreview {
+    /// The text content, up to the configured preview size
+    content: String,
+    /// Whether the content was cut short of the full artifact
+    truncated: bool,
+}
+
+/// A downscaled thumbnail of an image artifact
+#[derive(Debug, SimpleObject)]
+struct ImageArtifactPreview {
+    /// The MIME type of the thumbnail data
+    mime_type: &'static str,
+    /// The base64 encoded thumbnail data
+    data: String,
+    /// The width of the thumbnail in pixels
+    width: u32,
+    /// The height of the thumbnail in pixels
+    height: u32,
+}
+
+/// Whether artifacts of a MIME type can be previewed as text
+fn is_text_mime_type(mime_type: &str) -> bool {
+    mime_type.starts_with("text/") || mime_type == "application/json"
+}
+
+/// Get the MIME type of the artifact, guessed from its file name
+pub(super) fn artifact_mime_type(
+    manifest: &IoArgoprojWorkflowV1alpha1Artifact,
+) -> Result<&'static str, WorkflowParsingError> {
+    let filename = artifact_filename(manifest)?;
+    Ok(mime_guess::from_path(filename)
+        .first_raw()
+        .unwrap_or("application/octet-stream"))
+}
+
+/// Get the key of the artifact in s3 bucket
+pub(super) fn artifact_key(
+    manifest: &IoArgoprojWorkflowV1alpha1Artifact,
+) -> Result<&str, WorkflowParsingError> {
+    manifest
+        .s3
+        .as_ref()
+        .ok_or(WorkflowParsingError::UnrecognisedArtifactStore)?
+        .key
+        .as_deref()
+        .ok_or(WorkflowParsingError::MissingArtifactKey)
 }
 
 /// Get filename of the artifact in s3 bucket
-fn artifact_filename(
+pub(super) fn artifact_filename(
     manifest: &IoArgoprojWorkflowV1alpha1Artifact,
 ) -> Result<&str, WorkflowParsingError> {
-    let path = Path::new(
-        manifest
-            .s3
-            .as_ref()
-            .ok_or(WorkflowParsingError::UnrecognisedArtifactStore)?
-            .key
-            .as_ref()
-            .ok_or(WorkflowParsingError::MissingArtifactKey)?,
-    );
+    let path = Path::new(artifact_key(manifest)?);
     path.file_name()
         .unwrap_or_default()
         .to_str()
         .ok_or(WorkflowParsingError::InvalidArtifactFilename)
 }
 
+/// The assumed number of tasks in a workflow, when estimating the complexity of a query
+const TASKS_COMPLEXITY_FACTOR: usize = 10;
+
+/// The complexity of a field resolved with a request to S3
+const S3_REQUEST_COMPLEXITY: usize = 5;
+
+/// The complexity of a field resolved with a request to the Kubernetes API
+const KUBERNETES_REQUEST_COMPLEXITY: usize = 10;
+
+/// The complexity of a field which downloads content, such as logs or artifact previews
+const DOWNLOAD_COMPLEXITY: usize = 20;
+
 /// A Task created by a workflow
 #[allow(clippy::missing_docs_in_private_items)]
 #[derive(Debug, Clone)]
 struct Task {
     node_status: IoArgoprojWorkflowV1alpha1NodeStatus,
     depends: Vec<String>,
+    workflow_name: String,
+    visit: String,
+    pod_name: Option<String>,
+    retry_attempt: Option<u32>,
+    task_map: Arc<TaskMap>,
+    /// The pod running the task, fetched by the first field reporting on it
+    pod: OnceCell<Option<Pod>>,
+}
+
+impl Task {
+    /// The pod running the task, fetched once and shared by the fields reporting on it
+    async fn pod(&self, ctx: &Context<'_>) -> anyhow::Result<Option<&Pod>> {
+        let Some(pod_name) = self.pod_name.as_deref() else {
+            return Ok(None);
+        };
+        let pod = self
+            .pod
+            .get_or_try_init(|| async {
+                let client = setup_client(ctx).await?;
+                anyhow::Ok(get_pod(client, &self.visit, pod_name).await?)
+            })
+            .await?;
+        Ok(pod.as_ref())
+    }
 }
 
 #[Object]
@@ -457,12 +899,61 @@ impl Task {
         self.node_status.children.clone()
     }
 
+    /// The child tasks, such as the steps of a step group or the attempts of a retried task
+    #[graphql(complexity = "TASKS_COMPLEXITY_FACTOR * child_complexity")]
+    async fn children(&self) -> Vec<Task> {
+        self.node_status
+            .children
+            .iter()
+            .filter_map(|child| self.task_map.task(child))
+            .collect()
+    }
+
+    /// The name of the template the task was run from
+    async fn template_name(&self) -> Option<&str> {
+        node_template_name(&self.node_status)
+    }
+
+    /// The name of the pod running the task, if the task runs in a pod
+    async fn pod_name(&self) -> Option<&str> {
+        self.pod_name.as_deref()
+    }
+
+    /// The ID of the step group or DAG task which bounds this task
+    async fn boundary_id(&self) -> Option<&str> {
+        self.node_status.boundary_id.as_deref()
+    }
+
+    /// The attempt number, counting from zero, if the task is an attempt of a retried task
+    async fn retry_attempt(&self) -> Option<u32> {
+        self.retry_attempt
+    }
+
+    /// The inputs the task was run with
+    async fn inputs(&self) -> Option<Inputs<'_>> {
+        self.node_status.inputs.as_ref().map(Inputs)
+    }
+
+    /// The outputs produced by the task
+    async fn outputs(&self) -> Option<Outputs<'_>> {
+        self.node_status.outputs.as_ref().map(Outputs)
+    }
+
     /// Artifacts produced by a task
     async fn artifacts(&self) -> Vec<Artifact<'_>> {
         self.node_status
             .outputs
             .as_ref()
-            .map(|outputs| outputs.artifacts.iter().map(Artifact).collect::<Vec<_>>())
+            .map(|outputs| {
+                outputs
+                    .artifacts
+                    .iter()
+                    .map(|manifest| Artifact {
+                        manifest,
+                        task: self,
+                    })
+                    .collect::<Vec<_>>()
+            })
             .unwrap_or_default()
     }
 
@@ -485,51 +976,307 @@ impl Task {
     async fn message(&self) -> Option<&str> {
         self.node_status.message.as_deref()
     }
-}
 
-/// Fetch missing task information
-async fn fetch_missing_task_info(
-    mut url: Url,
-    token: Option<&Authorization<Bearer>>,
-    manifest: &IoArgoprojWorkflowV1alpha1WorkflowStatus,
-    metadata: &Metadata,
-) -> anyhow::Result<HashMap<String, IoArgoprojWorkflowV1alpha1NodeStatus>> {
-    let mut nodes = manifest.nodes.clone();
-    if nodes.is_empty() {
+    /// The number of completed and total pods of the task
+    async fn progress(&self) -> Option<Progress> {
+        self.node_status
+            .progress
+            .as_deref()
+            .and_then(Progress::parse)
+    }
+
+    /// The seconds of each resource, such as cpu, memory or nvidia.com/gpu, used by the task
+    async fn resources_duration(&self) -> &HashMap<String, i64> {
+        &self.node_status.resources_duration
+    }
+
+    /// The estimated time, in seconds, the task takes to run, based on previous runs
+    async fn estimated_duration(&self) -> Option<i64> {
+        self.node_status.estimated_duration
+    }
+
+    /// Kubernetes events of the pod running the task, such as scheduling failures or image pull errors
+    #[graphql(complexity = "KUBERNETES_REQUEST_COMPLEXITY + child_complexity")]
+    async fn events(&self, ctx: &Context<'_>) -> anyhow::Result<Vec<PodEvent>> {
+        let Some(pod_name) = self.pod_name.as_deref() else {
+            return Ok(Vec::new());
+        };
+        let client = setup_client(ctx).await?;
+        Ok(list_pod_events(client, &self.visit, pod_name).await?)
+    }
+
+    /// Conditions of the pod running the task, empty if the pod no longer exists
+    #[graphql(complexity = "KUBERNETES_REQUEST_COMPLEXITY + child_complexity")]
+    async fn pod_conditions(&self, ctx: &Context<'_>) -> anyhow::Result<Vec<PodCondition>> {
+        Ok(self.pod(ctx).await?.map(pod_conditions).unwrap_or_default())
+    }
+
+    /// Statuses of the containers of the pod running the task, empty if the pod no longer exists
+    ///
+    /// Terminations such as OOMKilled are reported here, as they are not recorded as events.
+    #[graphql(complexity = "KUBERNETES_REQUEST_COMPLEXITY + child_complexity")]
+    async fn container_statuses(&self, ctx: &Context<'_>) -> anyhow::Result<Vec<ContainerStatus>> {
+        Ok(self
+            .pod(ctx)
+            .await?
+            .map(container_statuses)
+            .unwrap_or_default())
+    }
+
+    /// Log lines produced by the task, optionally limited to those matching a substring or regular expression
+    #[graphql(complexity = "DOWNLOAD_COMPLEXITY + child_complexity")]
+    async fn logs(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(
+            default = 100,
+            validator(minimum = 1, maximum = 10000),
+            desc = "The number of most recent lines to return, counted after filtering by `grep`"
+        )]
+        tail_lines: i64,
+        since_time: Option<DateTime<Utc>>,
+        #[graphql(default = "main")] container: String,
+        grep: Option<String>,
+    ) -> anyhow::Result<Vec<LogEntry>> {
+        let grep = grep.as_deref().map(Regex::new).transpose()?;
+        let mut url = ctx.data_unchecked::<ArgoServerUrl>().deref().to_owned();
+        let auth_token = self
+            .task_map
+            .privileged_token
+            .as_ref()
+            .or(ctx.data_unchecked::<ValidatedAuthToken>().as_token());
         url.path_segments_mut().unwrap().extend([
             "api",
             "v1",
             "workflows",
-            &metadata.visit.to_string(),
-            &metadata.name,
+            &self.visit,
+            &self.workflow_name,
+            "log",
         ]);
-        let request = if let Some(token) = token {
-            CLIENT.get(url).bearer_auth(token.token())
-        } else {
-            CLIENT.get(url)
+        url.query_pairs_mut()
+            .append_pair(
+                "podName",
+                self.pod_name.as_deref().unwrap_or(&self.node_status.id),
+            )
+            .append_pair("logOptions.container", &container);
+        // Lines are filtered here, so the tail can only be taken by Argo when every line is kept
+        if grep.is_none() {
+            url.query_pairs_mut()
+                .append_pair("logOptions.tailLines", &tail_lines.to_string());
+        }
+        if let Some(since_time) = since_time {
+            url.query_pairs_mut().append_pair(
+                "logOptions.sinceTime.seconds",
+                &since_time.timestamp().to_string(),
+            );
+        }
+        debug!("Retrieving task logs from {url}");
+        let mut request = CLIENT.get(url).header("Accept", "text/plain");
+        if let Some(auth_token) = auth_token {
+            request = request.bearer_auth(auth_token.token());
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(CodedError::from_response(response).await.into());
+        }
+        // The log is streamed line by line, so at most the tail is held however long the log is
+        let tail_lines = tail_lines as usize;
+        let mut entries = VecDeque::with_capacity(tail_lines);
+        let mut keep_line = |line: &[u8]| {
+            let Some(result) = serde_json::from_slice::<LogResponse>(line)
+                .ok()
+                .and_then(|parsed| parsed.result)
+            else {
+                return;
+            };
+            if grep
+                .as_ref()
+                .is_none_or(|grep| grep.is_match(&result.content))
+            {
+                if entries.len() == tail_lines {
+                    entries.pop_front();
+                }
+                entries.push_back(LogEntry::from(result));
+            }
         };
-        nodes = request
-            .send()
-            .await?
-            .json::<APIResult<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
-            .await?
-            .into_result()?
-            .status
-            .unwrap() //  Safe as the status field is always present
-            .nodes;
+        let mut buffer = Vec::new();
+        let mut byte_stream = response.bytes_stream();
+        while let Some(chunk) = byte_stream.next().await {
+            buffer.extend_from_slice(&chunk?);
+            while let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+                keep_line(&buffer[..end]);
+                buffer.drain(..=end);
+            }
+        }
+        keep_line(&buffer);
+        Ok(entries.into())
+    }
+}
+
+/// Fetch missing task information, through the [`WorkflowDataLoader`] of the request if one is
+/// available and the workflow was not listed with a privileged token
+async fn fetch_missing_task_info(
+    ctx: &Context<'_>,
+    manifest: &IoArgoprojWorkflowV1alpha1WorkflowStatus,
+    metadata: &Metadata,
+) -> anyhow::Result<HashMap<String, IoArgoprojWorkflowV1alpha1NodeStatus>> {
+    if !manifest.nodes.is_empty() {
+        return Ok(manifest.nodes.clone());
+    }
+    let key = WorkflowKey {
+        visit: metadata.visit.to_string(),
+        name: metadata.name.clone(),
+        uid: metadata.uid.clone(),
+        archived: metadata.archived,
+    };
+    let workflow = match (
+        &metadata.privileged_token,
+        ctx.data_opt::<WorkflowDataLoader>(),
+    ) {
+        (Some(token), _) => {
+            let server_url = ctx.data_unchecked::<ArgoServerUrl>();
+            Arc::new(fetch_workflow_details(server_url, Some(token), &key).await?)
+        }
+        (None, Some(loader)) => loaded(loader.load_one(key).await)?,
+        (None, None) => {
+            let server_url = ctx.data_unchecked::<ArgoServerUrl>();
+            let token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+            Arc::new(fetch_workflow_details(server_url, token, &key).await?)
+        }
+    };
+    Ok(workflow
+        .status
+        .as_ref()
+        .map(|status| status.nodes.clone())
+        .unwrap_or_default())
+}
+
+/// Fetch the full manifest of a workflow, including the nodes omitted from list responses
+pub(super) async fn fetch_workflow_details(
+    server_url: &Url,
+    token: Option<&Authorization<Bearer>>,
+    key: &WorkflowKey,
+) -> anyhow::Result<IoArgoprojWorkflowV1alpha1Workflow> {
+    let mut url = server_url.clone();
+    if key.archived {
+        url.path_segments_mut()
+            .unwrap()
+            .extend(["api", "v1", "archived-workflows", &key.uid]);
+    } else {
+        url.path_segments_mut()
+            .unwrap()
+            .extend(["api", "v1", "workflows", &key.visit, &key.name]);
+    }
+    let request = if let Some(token) = token {
+        CLIENT.get(url).bearer_auth(token.token())
+    } else {
+        CLIENT.get(url)
+    };
+    Ok(request
+        .send()
+        .await?
+        .json::<APIResult<IoArgoprojWorkflowV1alpha1Workflow>>()
+        .await?
+        .into_result()?)
+}
+
+/// The annotation recording the pod name format of a workflow
+const POD_NAME_FORMAT_ANNOTATION: &str = "workflows.argoproj.io/pod-name-format";
+
+/// The name of the pod running a task, following the pod name format of the workflow
+pub(super) fn task_pod_name(
+    manifest: &IoArgoprojWorkflowV1alpha1Workflow,
+    node: &IoArgoprojWorkflowV1alpha1NodeStatus,
+) -> String {
+    pod_name(
+        manifest.metadata.name.as_deref().unwrap_or_default(),
+        manifest
+            .metadata
+            .annotations
+            .get(POD_NAME_FORMAT_ANNOTATION)
+            .map(String::as_str),
+        node,
+    )
+}
+
+/// The name of the template a node was run from
+fn node_template_name(node: &IoArgoprojWorkflowV1alpha1NodeStatus) -> Option<&str> {
+    node.template_ref
+        .as_ref()
+        .and_then(|template_ref| template_ref.template.as_deref())
+        .or(node.template_name.as_deref())
+}
+
+/// The name of the pod running a node of a workflow, following the given pod name format
+fn pod_name(
+    workflow_name: &str,
+    pod_name_format: Option<&str>,
+    node: &IoArgoprojWorkflowV1alpha1NodeStatus,
+) -> String {
+    /// The maximum length of a pod name prefix, leaving room for the node name hash
+    const MAX_POD_NAME_PREFIX_LENGTH: usize = 242;
+
+    if pod_name_format == Some("v1") {
+        return node.id.clone();
+    }
+    if node.name == workflow_name {
+        return workflow_name.to_string();
     }
-    Ok(nodes)
+    let template_name = node_template_name(node).unwrap_or_default();
+    let mut prefix = if template_name.is_empty() {
+        workflow_name.to_string()
+    } else {
+        format!("{workflow_name}-{template_name}")
+    };
+    prefix.truncate(MAX_POD_NAME_PREFIX_LENGTH);
+    // FNV-1a hash of the node name, as used by Argo Workflows
+    let hash = node.name.bytes().fold(0x811c9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x01000193)
+    });
+    format!("{prefix}-{hash}")
 }
 
-/// A wrapper for list of tasks
-struct TaskMap(HashMap<String, IoArgoprojWorkflowV1alpha1NodeStatus>);
+/// The tasks of a workflow, shared between them to resolve relationships
+#[derive(Debug)]
+struct TaskMap {
+    /// The nodes of the workflow, keyed by ID
+    nodes: HashMap<String, IoArgoprojWorkflowV1alpha1NodeStatus>,
+    /// The IDs of the parents of each node
+    parents: HashMap<String, Vec<String>>,
+    /// The name of the workflow
+    workflow_name: String,
+    /// The visit the workflow was run against
+    visit: String,
+    /// The format used to name the pods of the workflow, if not the default
+    pod_name_format: Option<String>,
+    /// The token with which a staff or admin view listed the workflow, if it was listed by one
+    privileged_token: Option<Authorization<Bearer>>,
+}
 
 impl TaskMap {
+    /// Creates a [`TaskMap`] from the nodes of a workflow
+    fn new(
+        nodes: HashMap<String, IoArgoprojWorkflowV1alpha1NodeStatus>,
+        metadata: &Metadata,
+    ) -> Self {
+        let parents = TaskMap::generate_relationship_map(&nodes);
+        Self {
+            nodes,
+            parents,
+            workflow_name: metadata.name.clone(),
+            visit: metadata.visit.to_string(),
+            pod_name_format: metadata.pod_name_format.clone(),
+            privileged_token: metadata.privileged_token.clone(),
+        }
+    }
+
     /// Generates a relationship map between parent and children
-    fn generate_relationship_map(&self) -> HashMap<String, Vec<String>> {
+    fn generate_relationship_map(
+        nodes: &HashMap<String, IoArgoprojWorkflowV1alpha1NodeStatus>,
+    ) -> HashMap<String, Vec<String>> {
         let mut parent_map: HashMap<String, Vec<String>> = HashMap::new();
 
-        for (node_name, node_status) in &self.0 {
+        for (node_name, node_status) in nodes {
             for child in &node_status.children {
                 parent_map
                     .entry(child.clone())
@@ -540,19 +1287,52 @@ impl TaskMap {
         parent_map
     }
 
-    /// Converts [`TaskMap`] into [`Vec<Task>`]`
-    fn into_tasks(self) -> Vec<Task> {
-        let mut relationship_map = TaskMap::generate_relationship_map(&self);
-        self.0
-            .into_iter()
-            .map(|(node_name, node_status)| {
-                let depends = relationship_map.remove(&node_name).unwrap_or_default();
-                Task {
-                    node_status,
-                    depends,
-                }
+    /// Creates the [`Task`] of a node
+    fn task(self: &Arc<Self>, id: &str) -> Option<Task> {
+        let node_status = self.nodes.get(id)?.clone();
+        let depends = self.parents.get(id).cloned().unwrap_or_default();
+        let pod_name = (node_status.type_ == "Pod").then(|| {
+            pod_name(
+                &self.workflow_name,
+                self.pod_name_format.as_deref(),
+                &node_status,
+            )
+        });
+        // Attempts of a retried task are named after it, suffixed with the attempt in parentheses
+        let retry_attempt = depends
+            .iter()
+            .filter_map(|parent| self.nodes.get(parent))
+            .any(|parent| parent.type_ == "Retry")
+            .then(|| {
+                node_status
+                    .name
+                    .strip_suffix(')')?
+                    .rsplit_once('(')?
+                    .1
+                    .parse()
+                    .ok()
             })
-            .collect::<Vec<_>>()
+            .flatten();
+        Some(Task {
+            node_status,
+            depends,
+            workflow_name: self.workflow_name.clone(),
+            visit: self.visit.clone(),
+            pod_name,
+            retry_attempt,
+            task_map: self.clone(),
+            pod: OnceCell::new(),
+        })
+    }
+
+    /// Converts [`TaskMap`] into [`Vec<Task>`]`
+    fn into_tasks(self) -> Vec<Task> {
+        let task_map = Arc::new(self);
+        task_map
+            .nodes
+            .keys()
+            .filter_map(|id| task_map.task(id))
+            .collect::<Vec<_>>()
     }
 }
 
@@ -582,22 +1362,19 @@ impl WorkflowsQuery {
         ctx: &Context<'_>,
         id: ID,
     ) -> anyhow::Result<Option<Workflow>> {
-        let id_str = id.to_string();
-        let parts: Vec<&str> = id_str.split(':').collect();
-        if parts.len() != 3 {
-            return Err(anyhow::anyhow!("Invalid Workflow ID"));
-        }
-        let visit_display = parts[0];
-        let workflow_name = parts[1];
-        let workflow_uid = parts[2];
-
-        let visit_input: VisitInput = visit_display.parse()?;
-
-        get_workflow_from_argo_api(ctx, visit_input, workflow_name, Some(workflow_uid)).await
+        let workflow_id = WorkflowId::from_str(&id)?;
+        get_workflow_from_argo_api(
+            ctx,
+            workflow_id.visit,
+            &workflow_id.name,
+            Some(&workflow_id.uid),
+        )
+        .await
     }
 
     /// Find all workflows available for a given visit
     #[instrument(name = "graph_proxy_workflows", skip(self, ctx))]
+    #[graphql(complexity = "limit.unwrap_or(10) as usize * child_complexity")]
     async fn workflows(
         &self,
         ctx: &Context<'_>,
@@ -605,70 +1382,903 @@ impl WorkflowsQuery {
         cursor: Option<String>,
         #[graphql(validator(minimum = 1, maximum = 30))] limit: Option<u32>,
         filter: Option<WorkflowFilter>,
+        #[graphql(default = false)] include_archived: bool,
     ) -> anyhow::Result<Connection<OpaqueCursor<usize>, Workflow, EmptyFields, EmptyFields>> {
-        let mut url = ctx.data_unchecked::<ArgoServerUrl>().deref().to_owned();
-        let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
-        url.path_segments_mut()
-            .unwrap()
-            .extend(["api", "v1", "workflows", &visit.to_string()]);
-
-        if let Some(filter) = &filter {
-            filter.generate_filters(&mut url);
-        }
-        let limit = limit.unwrap_or(10);
-        url.query_pairs_mut()
-            .append_pair("listOptions.limit", &limit.to_string());
-        let cursor_index = if let Some(cursor) = cursor {
-            let cursor_value = OpaqueCursor::<usize>::decode_cursor(&cursor)
-                .map_err(|_| anyhow::Error::msg("Cursor not valid"))?;
-            url.query_pairs_mut()
-                .append_pair("listOptions.continue", &cursor_value.0.to_string());
-            cursor_value.0
-        } else {
-            0
-        };
-        debug!("Retrieving workflows name from {url}");
-        let request = if let Some(auth_token) = auth_token {
-            CLIENT.get(url).bearer_auth(auth_token.token())
-        } else {
-            CLIENT.get(url)
-        };
-
-        let api_result = request
-            .send()
-            .await?
-            .json::<APIResult<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1WorkflowList>>()
+        let limit = limit.unwrap_or(10) as usize;
+        let cursor_index = decode_cursor_index(cursor)?;
+
+        let filter = filter.unwrap_or_default();
+        let proxy_filtering = filter.requires_proxy_filtering();
+
+        if !include_archived && !proxy_filtering {
+            let (workflows, continue_token) = list_workflows_from_argo_api(
+                ctx,
+                &visit,
+                &filter,
+                false,
+                Some(limit),
+                cursor_index.map(|cursor_index| cursor_index.to_string()),
+            )
             .await?;
+            let cursor_index = cursor_index.unwrap_or_default();
+            let mut connection = Connection::new(cursor_index > 0, continue_token.is_some());
+            connection
+                .edges
+                .extend(workflows.into_iter().enumerate().map(|(idx, workflow)| {
+                    let cursor = OpaqueCursor(cursor_index + idx + 1);
+                    Edge::new(cursor, workflow)
+                }));
+            return Ok(connection);
+        }
 
-        let workflows_response = match api_result.into_result() {
-            Ok(res) => res,
-            Err(err) => {
-                if err.message.as_deref() == Some("Unauthorized") {
-                    return Err(err.into());
+        // Workflows are merged, filtered or sorted in the proxy, so each listing is read until
+        // it holds enough matching workflows to fill the requested page
+        let cursor_index = cursor_index.unwrap_or_default();
+        let needed = needed_workflows(&filter, cursor_index, limit);
+        let (live, archived) = futures_util::try_join!(
+            collect_matching_workflows(&filter, needed, |page_size, continue_token| {
+                list_workflows_from_argo_api(
+                    ctx,
+                    &visit,
+                    &filter,
+                    false,
+                    Some(page_size),
+                    continue_token,
+                )
+            }),
+            async {
+                if include_archived {
+                    collect_matching_workflows(&filter, needed, |page_size, continue_token| {
+                        list_workflows_from_argo_api(
+                            ctx,
+                            &visit,
+                            &filter,
+                            true,
+                            Some(page_size),
+                            continue_token,
+                        )
+                    })
+                    .await
+                } else {
+                    Ok(Vec::new())
                 }
-                return Ok(Connection::new(false, false));
-            }
-        };
+            },
+        )?;
+        let mut workflows = merge_archived_workflows(live, archived);
+        sort_workflows(&mut workflows, filter.sort_order());
+        Ok(paginate_workflows(workflows, cursor_index, limit))
+    }
+
+    /// Find all workflows created by the authenticated user, across every visit they can access
+    ///
+    /// The `sort` order takes precedence over that of the filter.
+    #[instrument(name = "graph_proxy_my_workflows", skip(self, ctx))]
+    #[graphql(complexity = "limit.unwrap_or(10) as usize * child_complexity")]
+    async fn my_workflows(
+        &self,
+        ctx: &Context<'_>,
+        cursor: Option<String>,
+        #[graphql(validator(minimum = 1, maximum = 30))] limit: Option<u32>,
+        filter: Option<WorkflowFilter>,
+        sort: Option<WorkflowSortOrder>,
+        #[graphql(default = false)] include_archived: bool,
+    ) -> anyhow::Result<Connection<OpaqueCursor<usize>, Workflow, EmptyFields, EmptyFields>> {
+        let auth_token = get_auth_token(ctx)?;
+        let holder = token_holder(ctx, &auth_token).await?;
+        let visits = get_member_visits(ctx, &holder.preferred_username).await?;
+        let creator_id = CreatorId(holder.sub);
+        let filter = filter.unwrap_or_default().with_sort_order(sort);
+        let cursor_index = decode_cursor_index(cursor)?.unwrap_or_default();
+        let limit = limit.unwrap_or(10) as usize;
+
+        let workflows = list_visits_workflows(
+            ctx,
+            visits,
+            &filter,
+            Some(&creator_id),
+            include_archived,
+            needed_workflows(&filter, cursor_index, limit),
+            &auth_token,
+        )
+        .await?;
+        Ok(paginate_merged_workflows(
+            workflows,
+            &filter,
+            cursor_index,
+            limit,
+        ))
+    }
 
-        let workflows = workflows_response
-            .items
+    /// Find all workflows run on an instrument, across every visit, for staff of the instrument or
+    /// admins of its science group
+    #[instrument(name = "graph_proxy_instrument_workflows", skip(self, ctx))]
+    #[graphql(
+        guard = "StaffGuard::new(&instrument)",
+        complexity = "limit.unwrap_or(10) as usize * child_complexity"
+    )]
+    async fn instrument_workflows(
+        &self,
+        ctx: &Context<'_>,
+        instrument: String,
+        cursor: Option<String>,
+        #[graphql(validator(minimum = 1, maximum = 30))] limit: Option<u32>,
+        filter: Option<WorkflowFilter>,
+        #[graphql(default = false)] include_archived: bool,
+    ) -> anyhow::Result<Connection<OpaqueCursor<usize>, Workflow, EmptyFields, EmptyFields>> {
+        let visits = get_instrument_visits(ctx, &instrument).await?;
+        let auth_token = privileged_token(ctx).await?;
+        let bearer = Authorization::bearer(&auth_token)?;
+        let filter = filter.unwrap_or_default();
+        let cursor_index = decode_cursor_index(cursor)?.unwrap_or_default();
+        let limit = limit.unwrap_or(10) as usize;
+
+        let workflows = list_visits_workflows(
+            ctx,
+            visits,
+            &filter,
+            None,
+            include_archived,
+            needed_workflows(&filter, cursor_index, limit),
+            &auth_token,
+        )
+        .await?;
+        let workflows = workflows
             .into_iter()
-            .map(|workflow| Workflow::new(workflow, visit.clone().into()))
-            .collect::<Vec<_>>();
-        let mut connection = Connection::new(
-            cursor_index > 0,
-            workflows_response.metadata.continue_.is_some(),
-        );
+            .map(|workflow| workflow.with_privileged_token(&bearer))
+            .collect();
+        Ok(paginate_merged_workflows(
+            workflows,
+            &filter,
+            cursor_index,
+            limit,
+        ))
+    }
+
+    /// Find all live workflows across every visit, for platform admins
+    ///
+    /// Filters and sort orders which the Argo Server cannot apply are rejected if more than
+    /// [`MAX_PROXY_SCANNED_WORKFLOWS`] workflows would need to be listed to apply them.
+    #[instrument(name = "graph_proxy_all_workflows", skip(self, ctx))]
+    #[graphql(
+        guard = "AdminGuard",
+        complexity = "limit.unwrap_or(10) as usize * child_complexity"
+    )]
+    async fn all_workflows(
+        &self,
+        ctx: &Context<'_>,
+        cursor: Option<String>,
+        #[graphql(validator(minimum = 1, maximum = 30))] limit: Option<u32>,
+        filter: Option<WorkflowFilter>,
+    ) -> anyhow::Result<Connection<OpaqueCursor<usize>, Workflow, EmptyFields, EmptyFields>> {
+        let auth_token = privileged_token(ctx).await?;
+        let bearer = Authorization::bearer(&auth_token)?;
+        let limit = limit.unwrap_or(10) as usize;
+        let cursor_index = decode_cursor_index(cursor)?;
+        let filter = filter.unwrap_or_default();
+
+        if filter.requires_proxy_filtering() {
+            let cursor_index = cursor_index.unwrap_or_default();
+            let workflows = collect_matching_workflows(
+                &filter,
+                needed_workflows(&filter, cursor_index, limit),
+                |page_size, continue_token| {
+                    list_cluster_workflows_from_argo_api(
+                        ctx,
+                        &filter,
+                        Some(page_size),
+                        continue_token,
+                        &auth_token,
+                    )
+                },
+            )
+            .await?;
+            let workflows = workflows
+                .into_iter()
+                .map(|workflow| workflow.with_privileged_token(&bearer))
+                .collect();
+            return Ok(paginate_merged_workflows(
+                workflows,
+                &filter,
+                cursor_index,
+                limit,
+            ));
+        }
+
+        let (workflows, continue_token) = list_cluster_workflows_from_argo_api(
+            ctx,
+            &filter,
+            Some(limit),
+            cursor_index.map(|cursor_index| cursor_index.to_string()),
+            &auth_token,
+        )
+        .await?;
+        let cursor_index = cursor_index.unwrap_or_default();
+        let mut connection = Connection::new(cursor_index > 0, continue_token.is_some());
         connection
             .edges
             .extend(workflows.into_iter().enumerate().map(|(idx, workflow)| {
                 let cursor = OpaqueCursor(cursor_index + idx + 1);
-                Edge::new(cursor, workflow)
+                Edge::new(cursor, workflow.with_privileged_token(&bearer))
             }));
         Ok(connection)
     }
 }
 
+/// The number of workflows requested in each page of a listing filtered or sorted in the proxy
+const PROXY_LIST_PAGE_SIZE: usize = 200;
+
+/// The most workflows read from any one listing to fill a page filtered or sorted in the proxy
+const MAX_PROXY_SCANNED_WORKFLOWS: usize = 2000;
+
+/// Decode the index of the last workflow on the previous page from a cursor
+fn decode_cursor_index(cursor: Option<String>) -> anyhow::Result<Option<usize>> {
+    cursor
+        .map(|cursor| {
+            OpaqueCursor::<usize>::decode_cursor(&cursor)
+                .map(|cursor| cursor.0)
+                .map_err(|_| CodedError::bad_input("Cursor not valid").into())
+        })
+        .transpose()
+}
+
+/// A page of workflows which were merged, filtered or sorted in the proxy
+fn paginate_workflows(
+    workflows: Vec<Workflow>,
+    cursor_index: usize,
+    limit: usize,
+) -> Connection<OpaqueCursor<usize>, Workflow, EmptyFields, EmptyFields> {
+    let mut connection = Connection::new(cursor_index > 0, workflows.len() > cursor_index + limit);
+    connection.edges.extend(
+        workflows
+            .into_iter()
+            .enumerate()
+            .skip(cursor_index)
+            .take(limit)
+            .map(|(idx, workflow)| Edge::new(OpaqueCursor(idx + 1), workflow)),
+    );
+    connection
+}
+
+/// A page of matching workflows merged from several listings, once sorted in the proxy
+fn paginate_merged_workflows(
+    mut workflows: Vec<Workflow>,
+    filter: &WorkflowFilter,
+    cursor_index: usize,
+    limit: usize,
+) -> Connection<OpaqueCursor<usize>, Workflow, EmptyFields, EmptyFields> {
+    sort_workflows(&mut workflows, filter.sort_order());
+    paginate_workflows(workflows, cursor_index, limit)
+}
+
+/// The number of matching workflows needed from each listing to fill a page merged, filtered or
+/// sorted in the proxy, and to tell whether another page follows it
+///
+/// Workflows are listed newest first, so only those up to one past the requested page are
+/// needed, unless they are sorted otherwise, in which case every matching workflow is.
+fn needed_workflows(filter: &WorkflowFilter, cursor_index: usize, limit: usize) -> Option<usize> {
+    (filter.sort_order() == WorkflowSortOrder::NewestFirst).then_some(cursor_index + limit + 1)
+}
+
+/// Read a listing of workflows, page by page, until it holds the number of workflows matching the
+/// filter which are needed, or is exhausted
+///
+/// Fails, rather than returning a partial page or order, if more than
+/// [`MAX_PROXY_SCANNED_WORKFLOWS`] workflows would need to be read.
+async fn collect_matching_workflows<F, Fut>(
+    filter: &WorkflowFilter,
+    needed: Option<usize>,
+    mut list_page: F,
+) -> anyhow::Result<Vec<Workflow>>
+where
+    F: FnMut(usize, Option<String>) -> Fut,
+    Fut: Future<Output = anyhow::Result<(Vec<Workflow>, Option<String>)>>,
+{
+    let page_size = match needed {
+        Some(needed) if !filter.requires_proxy_filtering() => needed.min(PROXY_LIST_PAGE_SIZE),
+        _ => PROXY_LIST_PAGE_SIZE,
+    };
+    let mut workflows = Vec::new();
+    let mut scanned = 0;
+    let mut continue_token = None;
+    loop {
+        let (page, next_continue_token) = list_page(page_size, continue_token).await?;
+        scanned += page_size;
+        workflows.extend(
+            page.into_iter()
+                .filter(|workflow| filter.matches(&workflow.manifest)),
+        );
+        if next_continue_token.is_none() || needed.is_some_and(|needed| workflows.len() >= needed) {
+            return Ok(workflows);
+        }
+        if scanned >= MAX_PROXY_SCANNED_WORKFLOWS {
+            return Err(CodedError::bad_input(format!(
+                "More than {MAX_PROXY_SCANNED_WORKFLOWS} workflows would need to be filtered or \
+                 sorted, narrow the filter"
+            ))
+            .into());
+        }
+        continue_token = next_continue_token;
+    }
+}
+
+/// Sort workflows into the requested order
+fn sort_workflows(workflows: &mut [Workflow], sort_order: WorkflowSortOrder) {
+    match sort_order {
+        WorkflowSortOrder::NewestFirst => {
+            workflows.sort_by_key(|workflow| Reverse(workflow.creation_timestamp()))
+        }
+        WorkflowSortOrder::OldestFirst => {
+            workflows.sort_by_key(|workflow| workflow.creation_timestamp())
+        }
+        WorkflowSortOrder::LongestFirst => {
+            workflows.sort_by_key(|workflow| Reverse(workflow.duration()))
+        }
+        WorkflowSortOrder::ShortestFirst => workflows.sort_by_key(|workflow| {
+            let duration = workflow.duration();
+            (duration.is_none(), duration)
+        }),
+    }
+}
+
+/// Identify the holder of a token from its claims or, for opaque tokens, by introspection
+async fn token_holder(ctx: &Context<'_>, auth_token: &str) -> anyhow::Result<TokenHolder> {
+    if let Ok(data) = insecure_decode::<TokenHolder>(auth_token) {
+        return Ok(data.claims);
+    }
+    let holder = match ctx.data_opt::<TokenValidator>() {
+        Some(token_validator) => token_validator.introspected_holder(auth_token).await,
+        None => None,
+    };
+    holder.ok_or_else(|| anyhow::anyhow!("The holder of the token could not be identified"))
+}
+
+/// Get the visits of which a user is a member, from the ConfigMaps maintained by sessionspaces
+async fn get_member_visits(ctx: &Context<'_>, username: &str) -> anyhow::Result<Vec<VisitInput>> {
+    get_visits(ctx, |data| {
+        data.get("members")
+            .and_then(|members| from_str::<Vec<String>>(members).ok())
+            .is_some_and(|members| members.iter().any(|member| member == username))
+    })
+    .await
+}
+
+/// Get the visits run on an instrument, from the ConfigMaps maintained by sessionspaces
+async fn get_instrument_visits(
+    ctx: &Context<'_>,
+    instrument: &str,
+) -> anyhow::Result<Vec<VisitInput>> {
+    get_visits(ctx, |data| {
+        data.get("instrument")
+            .is_some_and(|visit_instrument| visit_instrument == instrument)
+    })
+    .await
+}
+
+/// Get the visits whose sessionspaces ConfigMap data matches a predicate, from the
+/// [`VisitCache`] if one is configured and has been populated
+async fn get_visits(
+    ctx: &Context<'_>,
+    predicate: impl Fn(&BTreeMap<String, String>) -> bool,
+) -> anyhow::Result<Vec<VisitInput>> {
+    let config_maps = match ctx
+        .data_opt::<VisitCache>()
+        .filter(|cache| cache.is_ready())
+    {
+        Some(cache) => cache.config_maps(),
+        None => {
+            let client = setup_client(ctx).await?;
+            let list_params = ListParams::default()
+                .labels(SESSIONSPACES_LABEL_SELECTOR)
+                .fields(SESSIONSPACES_FIELD_SELECTOR);
+            Api::<ConfigMap>::all(client)
+                .list(&list_params)
+                .await?
+                .items
+                .into_iter()
+                .map(Arc::new)
+                .collect()
+        }
+    };
+    Ok(config_maps
+        .into_iter()
+        .filter(|config_map| config_map.data.as_ref().is_some_and(&predicate))
+        .filter_map(|config_map| config_map.metadata.namespace.as_ref()?.parse().ok())
+        .collect())
+}
+
+/// Get the newest workflows matching the filter in each of several visits, or every matching
+/// workflow if none are needed
+async fn list_visits_workflows(
+    ctx: &Context<'_>,
+    visits: Vec<VisitInput>,
+    filter: &WorkflowFilter,
+    creator_id: Option<&CreatorId>,
+    include_archived: bool,
+    needed: Option<usize>,
+    auth_token: &str,
+) -> anyhow::Result<Vec<Workflow>> {
+    let mut workflows = Vec::new();
+    for visit_workflows in join_all(visits.into_iter().map(|visit| {
+        list_visit_workflows(
+            ctx,
+            visit,
+            filter,
+            creator_id,
+            include_archived,
+            needed,
+            auth_token,
+        )
+    }))
+    .await
+    {
+        workflows.extend(visit_workflows?);
+    }
+    Ok(workflows)
+}
+
+/// Get the newest workflows matching the filter in a visit from the Argo Workflows REST API,
+/// optionally only those created by a given user and optionally including those in the workflow
+/// archive
+async fn list_visit_workflows(
+    ctx: &Context<'_>,
+    visit: VisitInput,
+    filter: &WorkflowFilter,
+    creator_id: Option<&CreatorId>,
+    include_archived: bool,
+    needed: Option<usize>,
+    auth_token: &str,
+) -> anyhow::Result<Vec<Workflow>> {
+    let list_source = |archived| {
+        collect_matching_workflows(filter, needed, {
+            let visit = &visit;
+            move |page_size, continue_token| {
+                list_visit_workflows_from_source(
+                    ctx,
+                    visit,
+                    filter,
+                    creator_id,
+                    archived,
+                    page_size,
+                    continue_token,
+                    auth_token,
+                )
+            }
+        })
+    };
+    let live = list_source(false).await?;
+    if !include_archived {
+        return Ok(live);
+    }
+    let archived = list_source(true).await?;
+    Ok(merge_archived_workflows(live, archived))
+}
+
+/// Get a page of the newest workflows in a visit, optionally only those created by a given user,
+/// from either the live or archived workflows, along with the token continuing the listing
+#[allow(clippy::too_many_arguments)]
+async fn list_visit_workflows_from_source(
+    ctx: &Context<'_>,
+    visit: &VisitInput,
+    filter: &WorkflowFilter,
+    creator_id: Option<&CreatorId>,
+    archived: bool,
+    page_size: usize,
+    continue_token: Option<String>,
+    auth_token: &str,
+) -> anyhow::Result<(Vec<Workflow>, Option<String>)> {
+    let mut url = workflows_list_url(ctx, visit, archived);
+    match creator_id {
+        Some(creator_id) => filter.generate_creator_filters(&mut url, creator_id),
+        None => filter.generate_filters(&mut url),
+    }
+    if archived {
+        filter.generate_archive_filters(&mut url);
+    }
+    append_page_options(&mut url, Some(page_size), continue_token.as_deref());
+    debug!("Retrieving visit workflows from {url}");
+    let response = CLIENT.get(url).bearer_auth(auth_token).send().await?;
+    if !response.status().is_success() {
+        return Err(CodedError::from_response(response).await.into());
+    }
+    let workflows_response = response
+        .json::<ArgoList<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
+        .await?;
+
+    let continue_token = workflows_response.continue_token();
+    let workflows = workflows_response
+        .into_items()
+        .map(|workflow| new_listed_workflow(workflow, visit, archived))
+        .collect();
+    Ok((workflows, continue_token))
+}
+
+/// Get a page of workflows in a visit, from either the live or archived workflows, along with the
+/// token continuing the listing
+async fn list_workflows_from_argo_api(
+    ctx: &Context<'_>,
+    visit: &VisitInput,
+    filter: &WorkflowFilter,
+    archived: bool,
+    limit: Option<usize>,
+    continue_token: Option<String>,
+) -> anyhow::Result<(Vec<Workflow>, Option<String>)> {
+    let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+    let mut url = workflows_list_url(ctx, visit, archived);
+    filter.generate_filters(&mut url);
+    if archived {
+        filter.generate_archive_filters(&mut url);
+    }
+    append_page_options(&mut url, limit, continue_token.as_deref());
+    debug!("Retrieving workflows name from {url}");
+    let request = if let Some(auth_token) = auth_token {
+        CLIENT.get(url).bearer_auth(auth_token.token())
+    } else {
+        CLIENT.get(url)
+    };
+
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(CodedError::from_response(response).await.into());
+    }
+    let workflows_response = response
+        .json::<ArgoList<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
+        .await?;
+
+    let continue_token = workflows_response.continue_token();
+    let workflows = workflows_response
+        .into_items()
+        .map(|workflow| new_listed_workflow(workflow, visit, archived))
+        .collect();
+    Ok((workflows, continue_token))
+}
+
+/// Get a page of live workflows across every visit, along with the token continuing the listing
+///
+/// Workflows in namespaces which are not visits are omitted.
+async fn list_cluster_workflows_from_argo_api(
+    ctx: &Context<'_>,
+    filter: &WorkflowFilter,
+    limit: Option<usize>,
+    continue_token: Option<String>,
+    auth_token: &str,
+) -> anyhow::Result<(Vec<Workflow>, Option<String>)> {
+    let mut url = ctx.data_unchecked::<ArgoServerUrl>().deref().to_owned();
+    url.path_segments_mut()
+        .unwrap()
+        .extend(["api", "v1", "workflows", ""]);
+    filter.generate_filters(&mut url);
+    append_page_options(&mut url, limit, continue_token.as_deref());
+    debug!("Retrieving cluster workflows from {url}");
+    let response = CLIENT.get(url).bearer_auth(auth_token).send().await?;
+    if !response.status().is_success() {
+        return Err(CodedError::from_response(response).await.into());
+    }
+    let workflows_response = response
+        .json::<ArgoList<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
+        .await?;
+
+    let continue_token = workflows_response.continue_token();
+    let workflows = workflows_response
+        .into_items()
+        .filter_map(|workflow| {
+            let visit = workflow
+                .metadata
+                .namespace
+                .as_deref()?
+                .parse::<VisitInput>()
+                .ok()?;
+            Some(Workflow::new(workflow, visit.into()))
+        })
+        .collect();
+    Ok((workflows, continue_token))
+}
+
+/// A page of resources listed by the Argo Server
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+pub(super) struct ArgoList<T> {
+    /// The resources listed, which the Argo Server reports as null rather than empty when none match
+    #[serde(default)]
+    items: Option<Vec<T>>,
+    /// The metadata of the list, including the token continuing it
+    #[serde(default)]
+    metadata: argo_workflows_openapi::IoK8sApimachineryPkgApisMetaV1ListMeta,
+}
+
+impl<T> ArgoList<T> {
+    /// Whether further pages of resources are available
+    pub(super) fn has_more(&self) -> bool {
+        self.metadata.continue_.is_some()
+    }
+
+    /// The token continuing the listing, if further pages of resources are available
+    pub(super) fn continue_token(&self) -> Option<String> {
+        self.metadata.continue_.clone()
+    }
+
+    /// The resources listed in this page
+    pub(super) fn into_items(self) -> impl Iterator<Item = T> {
+        self.items.unwrap_or_default().into_iter()
+    }
+}
+
+/// Append the Argo Workflows REST API list options selecting a page
+fn append_page_options(url: &mut Url, limit: Option<usize>, continue_token: Option<&str>) {
+    if let Some(limit) = limit {
+        url.query_pairs_mut()
+            .append_pair("listOptions.limit", &limit.to_string());
+    }
+    if let Some(continue_token) = continue_token {
+        url.query_pairs_mut()
+            .append_pair("listOptions.continue", continue_token);
+    }
+}
+
+/// The Argo Workflows REST API URL listing the workflows of a visit, either live or archived
+fn workflows_list_url(ctx: &Context<'_>, visit: &VisitInput, archived: bool) -> Url {
+    let mut url = ctx.data_unchecked::<ArgoServerUrl>().deref().to_owned();
+    if archived {
+        url.path_segments_mut()
+            .unwrap()
+            .extend(["api", "v1", "archived-workflows"]);
+        url.query_pairs_mut()
+            .append_pair("namespace", &visit.to_string());
+    } else {
+        url.path_segments_mut()
+            .unwrap()
+            .extend(["api", "v1", "workflows", &visit.to_string()]);
+    }
+    url
+}
+
+/// Create a [`Workflow`] from a list response, marking it as archived if it came from the archive
+fn new_listed_workflow(
+    manifest: IoArgoprojWorkflowV1alpha1Workflow,
+    visit: &VisitInput,
+    archived: bool,
+) -> Workflow {
+    if archived {
+        Workflow::from_archive(manifest, visit.clone().into())
+    } else {
+        Workflow::new(manifest, visit.clone().into())
+    }
+}
+
+/// Combine live and archived workflows, preferring the live copy of any workflow present in both
+fn merge_archived_workflows(mut live: Vec<Workflow>, archived: Vec<Workflow>) -> Vec<Workflow> {
+    let live_uids = live
+        .iter()
+        .map(|workflow| workflow.metadata.uid.clone())
+        .collect::<HashSet<_>>();
+    live.extend(
+        archived
+            .into_iter()
+            .filter(|workflow| !live_uids.contains(&workflow.metadata.uid)),
+    );
+    live
+}
+
+/// The components of a [`Workflow`] ID, in the form `visit:name:uid`
+#[derive(Debug, Clone)]
+struct WorkflowId {
+    /// The visit the workflow was run against
+    visit: VisitInput,
+    /// The name of the workflow
+    name: String,
+    /// The unique ID of the workflow
+    uid: String,
+}
+
+impl FromStr for WorkflowId {
+    type Err = anyhow::Error;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = id.split(':').collect();
+        if parts.len() != 3 {
+            return Err(CodedError::bad_input("Invalid Workflow ID").into());
+        }
+        Ok(Self {
+            visit: parts[0].parse()?,
+            name: parts[1].to_string(),
+            uid: parts[2].to_string(),
+        })
+    }
+}
+
+/// Mutations related to [`Workflow`]s
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowsMutation;
+
+#[Object(guard = "AuthGuard")]
+impl WorkflowsMutation {
+    /// Stop a [`Workflow`], allowing any exit handlers to run
+    #[instrument(name = "graph_proxy_stop_workflow", skip(self, ctx))]
+    async fn stop_workflow(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        message: Option<String>,
+        node_field_selector: Option<String>,
+    ) -> anyhow::Result<Workflow> {
+        let workflow_id = WorkflowId::from_str(&id)?;
+        let request = IoArgoprojWorkflowV1alpha1WorkflowStopRequest {
+            name: Some(workflow_id.name.clone()),
+            namespace: Some(workflow_id.visit.to_string()),
+            message,
+            node_field_selector,
+        };
+        update_workflow_in_argo_api(ctx, workflow_id, "stop", &request).await
+    }
+
+    /// Terminate a [`Workflow`] immediately, without running exit handlers
+    #[instrument(name = "graph_proxy_terminate_workflow", skip(self, ctx))]
+    async fn terminate_workflow(&self, ctx: &Context<'_>, id: ID) -> anyhow::Result<Workflow> {
+        let workflow_id = WorkflowId::from_str(&id)?;
+        let request = IoArgoprojWorkflowV1alpha1WorkflowTerminateRequest {
+            name: Some(workflow_id.name.clone()),
+            namespace: Some(workflow_id.visit.to_string()),
+        };
+        update_workflow_in_argo_api(ctx, workflow_id, "terminate", &request).await
+    }
+
+    /// Retry a failed or errored [`Workflow`] in place
+    ///
+    /// A node field selector (e.g. `displayName=step-a`) may be provided to retry only the
+    /// matching tasks, in which case `restartSuccessful` also re-runs matching tasks which
+    /// previously succeeded.
+    #[instrument(name = "graph_proxy_retry_workflow", skip(self, ctx))]
+    async fn retry_workflow(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        node_field_selector: Option<String>,
+        restart_successful: Option<bool>,
+    ) -> anyhow::Result<Workflow> {
+        let workflow_id = WorkflowId::from_str(&id)?;
+        let request = IoArgoprojWorkflowV1alpha1WorkflowRetryRequest {
+            name: Some(workflow_id.name.clone()),
+            namespace: Some(workflow_id.visit.to_string()),
+            node_field_selector,
+            restart_successful,
+            ..Default::default()
+        };
+        update_workflow_in_argo_api(ctx, workflow_id, "retry", &request).await
+    }
+
+    /// Resubmit a [`Workflow`], creating a new workflow with the same specification
+    #[instrument(name = "graph_proxy_resubmit_workflow", skip(self, ctx))]
+    async fn resubmit_workflow(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        memoized: Option<bool>,
+    ) -> anyhow::Result<Workflow> {
+        let workflow_id = WorkflowId::from_str(&id)?;
+        let request = IoArgoprojWorkflowV1alpha1WorkflowResubmitRequest {
+            name: Some(workflow_id.name.clone()),
+            namespace: Some(workflow_id.visit.to_string()),
+            memoized,
+            ..Default::default()
+        };
+        update_workflow_in_argo_api(ctx, workflow_id, "resubmit", &request).await
+    }
+
+    /// Suspend a running [`Workflow`]
+    #[instrument(name = "graph_proxy_suspend_workflow", skip(self, ctx))]
+    async fn suspend_workflow(&self, ctx: &Context<'_>, id: ID) -> anyhow::Result<Workflow> {
+        let workflow_id = WorkflowId::from_str(&id)?;
+        let request = IoArgoprojWorkflowV1alpha1WorkflowSuspendRequest {
+            name: Some(workflow_id.name.clone()),
+            namespace: Some(workflow_id.visit.to_string()),
+        };
+        update_workflow_in_argo_api(ctx, workflow_id, "suspend", &request).await
+    }
+
+    /// Resume a suspended [`Workflow`]
+    #[instrument(name = "graph_proxy_resume_workflow", skip(self, ctx))]
+    async fn resume_workflow(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        node_field_selector: Option<String>,
+    ) -> anyhow::Result<Workflow> {
+        let workflow_id = WorkflowId::from_str(&id)?;
+        let request = IoArgoprojWorkflowV1alpha1WorkflowResumeRequest {
+            name: Some(workflow_id.name.clone()),
+            namespace: Some(workflow_id.visit.to_string()),
+            node_field_selector,
+        };
+        update_workflow_in_argo_api(ctx, workflow_id, "resume", &request).await
+    }
+
+    /// Delete a [`Workflow`], returning its final state
+    #[instrument(name = "graph_proxy_delete_workflow", skip(self, ctx))]
+    async fn delete_workflow(&self, ctx: &Context<'_>, id: ID) -> anyhow::Result<Workflow> {
+        let workflow_id = WorkflowId::from_str(&id)?;
+        let workflow = get_workflow_from_argo_api(
+            ctx,
+            workflow_id.visit.clone(),
+            &workflow_id.name,
+            Some(&workflow_id.uid),
+        )
+        .await?
+        .ok_or_else(|| CodedError::not_found("Workflow not found"))?;
+
+        let server_url = ctx.data_unchecked::<ArgoServerUrl>().deref();
+        let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+        let mut url = server_url.clone();
+        url.path_segments_mut().unwrap().extend([
+            "api",
+            "v1",
+            "workflows",
+            &workflow_id.visit.to_string(),
+            &workflow_id.name,
+        ]);
+        url.query_pairs_mut()
+            .append_pair("deleteOptions.preconditions.uid", &workflow_id.uid);
+        debug!("Deleting workflow at {url}");
+        let request = if let Some(auth_token) = auth_token {
+            CLIENT.delete(url).bearer_auth(auth_token.token())
+        } else {
+            CLIENT.delete(url)
+        };
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(CodedError::from_response(response).await.into());
+        }
+        Ok(workflow)
+    }
+}
+
+/// Apply a lifecycle action (e.g. `stop`, `retry`) to a workflow via the Argo Workflows REST API
+///
+/// The workflow currently holding the name is checked to have the uid of the ID first, so the
+/// action is not applied to a later workflow which reused the name.
+async fn update_workflow_in_argo_api(
+    ctx: &Context<'_>,
+    workflow_id: WorkflowId,
+    action: &str,
+    body: &impl Serialize,
+) -> anyhow::Result<Workflow> {
+    get_workflow_from_argo_api(
+        ctx,
+        workflow_id.visit.clone(),
+        &workflow_id.name,
+        Some(&workflow_id.uid),
+    )
+    .await?
+    .filter(|workflow| workflow.metadata.uid == workflow_id.uid)
+    .ok_or_else(|| CodedError::not_found("Workflow not found"))?;
+
+    let server_url = ctx.data_unchecked::<ArgoServerUrl>().deref();
+    let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+    let mut url = server_url.clone();
+    url.path_segments_mut().unwrap().extend([
+        "api",
+        "v1",
+        "workflows",
+        &workflow_id.visit.to_string(),
+        &workflow_id.name,
+        action,
+    ]);
+    debug!("Updating workflow at {url}");
+    let request = if let Some(auth_token) = auth_token {
+        CLIENT.put(url).bearer_auth(auth_token.token())
+    } else {
+        CLIENT.put(url)
+    }
+    .json(body);
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(CodedError::from_response(response).await.into());
+    }
+    let workflow = response
+        .json::<APIResult<IoArgoprojWorkflowV1alpha1Workflow>>()
+        .await?
+        .into_result()?;
+    Ok(Workflow::new(workflow, workflow_id.visit.into()))
+}
+
 /// Get single workflow from Argo Workflows REST API
 async fn get_workflow_from_argo_api(
     ctx: &Context<'_>,
@@ -678,6 +2288,17 @@ async fn get_workflow_from_argo_api(
 ) -> anyhow::Result<Option<Workflow>> {
     let server_url = ctx.data_unchecked::<ArgoServerUrl>().deref();
     let auth_token = ctx.data_unchecked::<ValidatedAuthToken>().as_token();
+    fetch_workflow_from_argo_api(server_url, auth_token, visit, name, uid).await
+}
+
+/// Fetch single workflow from Argo Workflows REST API, falling back to the workflow archive
+pub(super) async fn fetch_workflow_from_argo_api(
+    server_url: &Url,
+    auth_token: Option<&Authorization<Bearer>>,
+    visit: VisitInput,
+    name: &str,
+    uid: Option<&str>,
+) -> anyhow::Result<Option<Workflow>> {
     let mut url = server_url.clone();
     url.path_segments_mut()
         .unwrap()
@@ -692,7 +2313,7 @@ async fn get_workflow_from_argo_api(
     };
     let response = request.send().await?;
     if response.status() == reqwest::StatusCode::NOT_FOUND {
-        return Ok(None);
+        return get_workflow_from_argo_archive(server_url, auth_token, visit, name, uid).await;
     }
     let workflow = response
         .json::<APIResult<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
@@ -701,10 +2322,68 @@ async fn get_workflow_from_argo_api(
     Ok(Some(Workflow::new(workflow, visit.into())))
 }
 
-/// Information about the creator of a workflow.
-#[derive(Debug, Clone, SimpleObject, Eq, PartialEq)]
-struct WorkflowCreator {
-    /// An identifier unique to the creator of the workflow.
+/// Get single workflow from the workflow archive of the Argo Workflows REST API
+///
+/// In case of two workflows with the same name and no uid, returns the most recent.
+async fn get_workflow_from_argo_archive(
+    server_url: &Url,
+    auth_token: Option<&Authorization<Bearer>>,
+    visit: VisitInput,
+    name: &str,
+    uid: Option<&str>,
+) -> anyhow::Result<Option<Workflow>> {
+    let mut url = server_url.clone();
+    url.path_segments_mut()
+        .unwrap()
+        .extend(["api", "v1", "archived-workflows"]);
+    if let Some(uid) = uid {
+        url.path_segments_mut().unwrap().push(uid);
+    }
+    url.query_pairs_mut()
+        .append_pair("namespace", &visit.to_string());
+    if uid.is_none() {
+        url.query_pairs_mut()
+            .append_pair(
+                "listOptions.fieldSelector",
+                &format!("metadata.name={name}"),
+            )
+            .append_pair("listOptions.limit", "1");
+    }
+    debug!("Retrieving archived workflow from {url}");
+    let mut request = CLIENT.get(url);
+    if let Some(auth_token) = auth_token {
+        request = request.bearer_auth(auth_token.token());
+    };
+    let response = request.send().await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let workflow = if uid.is_some() {
+        Some(
+            response
+                .json::<APIResult<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
+                .await?
+                .into_result()?,
+        )
+    } else {
+        response
+            .json::<ArgoList<argo_workflows_openapi::IoArgoprojWorkflowV1alpha1Workflow>>()
+            .await?
+            .into_items()
+            .next()
+    };
+    Ok(workflow
+        .filter(|workflow| {
+            workflow.metadata.namespace.as_deref() == Some(&visit.to_string())
+                && workflow.metadata.name.as_deref() == Some(name)
+        })
+        .map(|workflow| Workflow::from_archive(workflow, visit.into())))
+}
+
+/// Information about the creator of a workflow.
+#[derive(Debug, Clone, SimpleObject, Eq, PartialEq)]
+struct WorkflowCreator {
+    /// An identifier unique to the creator of the workflow.
     /// Typically this is the creator's Fed-ID.
     creator_id: String,
 }
@@ -731,13 +2410,26 @@ impl WorkflowCreator {
 
 #[cfg(test)]
 mod tests {
+    use super::{task_pod_name, MAX_PROXY_SCANNED_WORKFLOWS, PROXY_LIST_PAGE_SIZE};
     use crate::graphql::auth_guard::AuthErrorCode;
-    use crate::graphql::{root_schema_builder, Authorization, Visit};
+    use crate::graphql::{
+        loaders::LoaderState, root_schema_builder, Authorization, RoleArgs, Visit,
+    };
+    use crate::metrics::{noop::NoopMeterProvider, Metrics};
+    use crate::s3client::{ArtifactUrlArgs, ArtifactUrlMode};
+    use crate::test_kubeconfig::TestKubeconfig;
     use crate::validate_token::ValidatedAuthToken;
-    use crate::{ArgoServerUrl, Client, S3Bucket, S3ClientArgs};
+    use crate::{
+        ArgoServerUrl, ArtifactMetadataCache, ArtifactPreviewArgs, Client, KubernetesApiUrl,
+        S3Bucket, S3ClientArgs,
+    };
+    use argo_workflows_openapi::{
+        IoArgoprojWorkflowV1alpha1NodeStatus, IoArgoprojWorkflowV1alpha1Workflow,
+    };
+    use jsonwebtoken::{encode, EncodingKey, Header};
     use rstest::rstest;
     use serde_json::json;
-    use std::path::PathBuf;
+    use std::{io::Cursor, path::PathBuf, sync::Arc};
     use url::Url;
 
     fn test_token() -> ValidatedAuthToken {
@@ -918,8 +2610,8 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn single_failed_workflow_query() {
-        let workflow_name = "numpy-benchmark-qhb59";
+    async fn task_logs_query() {
+        let workflow_name = "numpy-benchmark-wdkwj";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -929,7 +2621,7 @@ mod tests {
         let mut server = mockito::Server::new_async().await;
         let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-qhb59-failed.json");
+        response_file_path.push("get-workflow-wdkwj.json");
         let workflow_endpoint = server
             .mock(
                 "GET",
@@ -940,6 +2632,48 @@ mod tests {
             .with_body_from_file(response_file_path)
             .create_async()
             .await;
+        let log_lines = [
+            "Benchmarking numpy",
+            "ERROR: matrix multiplication failed",
+            "Error: retrying with smaller matrices",
+        ]
+        .iter()
+        .map(|content| {
+            json!({"result": {"content": content, "podName": workflow_name}}).to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+        let logs_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}/log")[..],
+            )
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("podName".to_string(), workflow_name.to_string()),
+                mockito::Matcher::UrlEncoded(
+                    "logOptions.container".to_string(),
+                    "main".to_string(),
+                ),
+                mockito::Matcher::UrlEncoded(
+                    "logOptions.sinceTime.seconds".to_string(),
+                    "1732009546".to_string(),
+                ),
+            ]))
+            .with_status(200)
+            .with_body(log_lines)
+            .create_async()
+            .await;
+        // The tail is taken after filtering, so must not be taken by Argo
+        let tailed_logs_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}/log")[..],
+            )
+            .match_query(mockito::Matcher::Regex("logOptions.tailLines".to_string()))
+            .with_status(500)
+            .expect(0)
+            .create_async()
+            .await;
 
         let argo_server_url = Url::parse(&server.url()).unwrap();
         let schema = root_schema_builder()
@@ -951,12 +2685,12 @@ mod tests {
             query {{
                 workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
                     status {{
-                        ...on WorkflowFailedStatus {{
-                            startTime
-                            endTime
-                            message
+                        ...on WorkflowSucceededStatus {{
                             tasks {{
-                                id
+                                logs(tailLines: 1, sinceTime: "2024-11-19T09:45:46Z", grep: "(?i)^error") {{
+                                    content
+                                    podName
+                                }}
                             }}
                         }}
                     }}
@@ -968,13 +2702,19 @@ mod tests {
         let resp = schema.execute(query).await.into_result().unwrap();
 
         workflow_endpoint.assert_async().await;
+        logs_endpoint.assert_async().await;
+        tailed_logs_endpoint.assert_async().await;
         let expected_data = json!({
             "workflow": {
                 "status": {
-                    "startTime": "2024-10-02T11:11:17+00:00",
-                    "endTime": "2024-10-02T11:12:32+00:00",
-                    "message": "OOMKilled (exit code 137)",
-                    "tasks": [{"id": workflow_name}]
+                    "tasks": [{
+                        "logs": [
+                            {
+                                "content": "Error: retrying with smaller matrices",
+                                "podName": workflow_name
+                            }
+                        ]
+                    }]
                 }
             }
         });
@@ -982,18 +2722,54 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn single_errored_workflow_query() {
-        let workflow_name = "numpy-benchmark-jwcfp";
+    async fn retried_task_tree_query() {
+        let workflow_name = "retry-wf";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
             number: 1,
         };
+        let pod = |id: &str, name: &str, phase: &str| {
+            json!({
+                "id": id,
+                "name": name,
+                "displayName": name,
+                "type": "Pod",
+                "templateName": "main",
+                "boundaryId": workflow_name,
+                "phase": phase,
+                "children": []
+            })
+        };
+        let workflow = json!({
+            "metadata": {
+                "name": workflow_name,
+                "namespace": visit.to_string(),
+                "uid": "6bd1a4c2-1d0c-4e8b-9f5e-0a1b2c3d4e5f",
+                "creationTimestamp": "2024-11-19T09:45:00Z"
+            },
+            "spec": {},
+            "status": {
+                "phase": "Succeeded",
+                "startedAt": "2024-11-19T09:45:00Z",
+                "finishedAt": "2024-11-19T09:47:00Z",
+                "nodes": {
+                    "retry-wf": {
+                        "id": "retry-wf",
+                        "name": workflow_name,
+                        "displayName": workflow_name,
+                        "type": "Retry",
+                        "templateName": "main",
+                        "phase": "Succeeded",
+                        "children": ["retry-wf-1", "retry-wf-2"]
+                    },
+                    "retry-wf-1": pod("retry-wf-1", "retry-wf(0)", "Failed"),
+                    "retry-wf-2": pod("retry-wf-2", "retry-wf(1)", "Succeeded")
+                }
+            }
+        });
 
         let mut server = mockito::Server::new_async().await;
-        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-jwcfp-errored.json");
         let workflow_endpoint = server
             .mock(
                 "GET",
@@ -1001,7 +2777,7 @@ mod tests {
             )
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body_from_file(response_file_path)
+            .with_body(workflow.to_string())
             .create_async()
             .await;
 
@@ -1015,12 +2791,18 @@ mod tests {
             query {{
                 workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
                     status {{
-                        ...on WorkflowErroredStatus {{
-                            startTime
-                            endTime
-                            message
+                        ...on WorkflowSucceededStatus {{
                             tasks {{
                                 id
+                                templateName
+                                podName
+                                boundaryId
+                                retryAttempt
+                                children {{
+                                    id
+                                    podName
+                                    retryAttempt
+                                }}
                             }}
                         }}
                     }}
@@ -1029,35 +2811,162 @@ mod tests {
         "#,
             workflow_name, visit.proposal_code, visit.proposal_number, visit.number
         );
-        let resp = schema.execute(query).await.into_result().unwrap();
 
+        let resp = schema.execute(&query).await.into_result().unwrap();
         workflow_endpoint.assert_async().await;
-        let expected_data = json!({
-            "workflow": {
-                "status": {
-                    "startTime": "2024-10-11T14:35:37+00:00",
-                    "endTime": "2024-10-11T14:35:37+00:00",
-                    "message": "error in entry template execution: Error applying PodSpecPatch",
-                    "tasks": [{"id": workflow_name}]
-                }
+
+        let resp = resp.data.into_json().unwrap();
+        let mut tasks = resp["workflow"]["status"]["tasks"]
+            .as_array()
+            .unwrap()
+            .clone();
+        tasks.sort_by_key(|task| task["id"].as_str().unwrap().to_string());
+        let expected = json!([
+            {
+                "id": "retry-wf",
+                "templateName": "main",
+                "podName": null,
+                "boundaryId": null,
+                "retryAttempt": null,
+                "children": [
+                    {
+                        "id": "retry-wf-1",
+                        "podName": "retry-wf-main-4265465332",
+                        "retryAttempt": 0
+                    },
+                    {
+                        "id": "retry-wf-2",
+                        "podName": "retry-wf-main-3661323953",
+                        "retryAttempt": 1
+                    }
+                ]
+            },
+            {
+                "id": "retry-wf-1",
+                "templateName": "main",
+                "podName": "retry-wf-main-4265465332",
+                "boundaryId": "retry-wf",
+                "retryAttempt": 0,
+                "children": []
+            },
+            {
+                "id": "retry-wf-2",
+                "templateName": "main",
+                "podName": "retry-wf-main-3661323953",
+                "boundaryId": "retry-wf",
+                "retryAttempt": 1,
+                "children": []
             }
-        });
-        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+        ]);
+        assert_eq!(json!(tasks), expected);
     }
 
     #[tokio::test]
-    async fn single_running_workflow_query() {
-        let workflow_name = "numpy-benchmark-kc7pf";
+    async fn pending_task_events_and_pod_conditions_query() {
+        let workflow_name = "pending-wf";
+        let pod_name = "pending-wf-1";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
             number: 1,
         };
+        let workflow = json!({
+            "metadata": {
+                "name": workflow_name,
+                "namespace": visit.to_string(),
+                "uid": "0c1d2e3f-4a5b-4c6d-8e7f-8091a2b3c4d5",
+                "creationTimestamp": "2024-11-19T09:45:00Z",
+                "annotations": {
+                    "workflows.argoproj.io/pod-name-format": "v1"
+                }
+            },
+            "spec": {},
+            "status": {
+                "phase": "Running",
+                "startedAt": "2024-11-19T09:45:00Z",
+                "nodes": {
+                    pod_name: {
+                        "id": pod_name,
+                        "name": workflow_name,
+                        "displayName": workflow_name,
+                        "type": "Pod",
+                        "templateName": "main",
+                        "phase": "Pending",
+                        "children": []
+                    }
+                }
+            }
+        });
+        let events = json!({
+            "kind": "EventList",
+            "apiVersion": "v1",
+            "metadata": {},
+            "items": [
+                {
+                    "metadata": {"name": "pending-wf-1.2", "namespace": visit.to_string()},
+                    "involvedObject": {"kind": "Pod", "name": pod_name},
+                    "type": "Warning",
+                    "reason": "BackOff",
+                    "message": "Back-off pulling image \"ghcr.io/example/missing:latest\"",
+                    "count": 4,
+                    "firstTimestamp": "2024-11-19T09:45:20Z",
+                    "lastTimestamp": "2024-11-19T09:46:30Z",
+                    "source": {"component": "kubelet"}
+                },
+                {
+                    "metadata": {"name": "pending-wf-1.1", "namespace": visit.to_string()},
+                    "involvedObject": {"kind": "Pod", "name": pod_name},
+                    "type": "Normal",
+                    "reason": "Scheduled",
+                    "message": "Successfully assigned mg36964-1/pending-wf-1 to node-1",
+                    "count": 1,
+                    "firstTimestamp": "2024-11-19T09:45:05Z",
+                    "lastTimestamp": "2024-11-19T09:45:05Z",
+                    "source": {"component": "default-scheduler"}
+                }
+            ]
+        });
+        let pod = json!({
+            "kind": "Pod",
+            "apiVersion": "v1",
+            "metadata": {"name": pod_name, "namespace": visit.to_string()},
+            "status": {
+                "phase": "Pending",
+                "conditions": [
+                    {
+                        "type": "PodScheduled",
+                        "status": "True",
+                        "lastTransitionTime": "2024-11-19T09:45:05Z"
+                    },
+                    {
+                        "type": "Ready",
+                        "status": "False",
+                        "reason": "ContainersNotReady",
+                        "message": "containers with unready status: [main]",
+                        "lastTransitionTime": "2024-11-19T09:45:05Z"
+                    }
+                ],
+                "containerStatuses": [
+                    {
+                        "name": "main",
+                        "image": "ghcr.io/example/missing:latest",
+                        "imageID": "",
+                        "ready": false,
+                        "restartCount": 0,
+                        "state": {
+                            "waiting": {
+                                "reason": "ImagePullBackOff",
+                                "message": "Back-off pulling image \"ghcr.io/example/missing:latest\""
+                            }
+                        }
+                    }
+                ]
+            }
+        });
 
         let mut server = mockito::Server::new_async().await;
-        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-kc7pf-running.json");
+        let _kubeconfig = TestKubeconfig::new(&server.url()).await.unwrap();
+
         let workflow_endpoint = server
             .mock(
                 "GET",
@@ -1065,13 +2974,35 @@ mod tests {
             )
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body_from_file(response_file_path)
+            .with_body(workflow.to_string())
+            .create_async()
+            .await;
+        let events_endpoint = server
+            .mock("GET", &format!("/api/v1/namespaces/{visit}/events")[..])
+            .match_query(mockito::Matcher::UrlEncoded(
+                "fieldSelector".to_string(),
+                format!("involvedObject.kind=Pod,involvedObject.name={pod_name}"),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(events.to_string())
+            .create_async()
+            .await;
+        let pod_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/namespaces/{visit}/pods/{pod_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(pod.to_string())
+            .expect(1)
             .create_async()
             .await;
 
-        let argo_server_url = Url::parse(&server.url()).unwrap();
         let schema = root_schema_builder()
-            .data(ArgoServerUrl(argo_server_url))
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(KubernetesApiUrl(server.url().parse().unwrap()))
             .data(test_token())
             .finish();
         let query = format!(
@@ -1080,12 +3011,25 @@ mod tests {
                 workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
                     status {{
                         ...on WorkflowRunningStatus {{
-                            startTime
-                            message
                             tasks {{
-                                id
-                                startTime
-                                endTime
+                                events {{
+                                    type
+                                    reason
+                                    count
+                                    lastTimestamp
+                                    source
+                                }}
+                                podConditions {{
+                                    type
+                                    status
+                                    reason
+                                }}
+                                containerStatuses {{
+                                    name
+                                    state
+                                    reason
+                                    exitCode
+                                }}
                             }}
                         }}
                     }}
@@ -1094,19 +3038,53 @@ mod tests {
         "#,
             workflow_name, visit.proposal_code, visit.proposal_number, visit.number
         );
-        let resp = schema.execute(query).await.into_result().unwrap();
+        let resp = schema.execute(&query).await.into_result().unwrap();
 
         workflow_endpoint.assert_async().await;
+        events_endpoint.assert_async().await;
+        pod_endpoint.assert_async().await;
+
         let expected_data = json!({
             "workflow": {
                 "status": {
-                    "startTime": "2025-01-22T12:29:45+00:00",
-                    "message": null,
                     "tasks": [
                         {
-                            "id": workflow_name,
-                            "startTime": "2025-01-22T12:29:45+00:00",
-                            "endTime": null
+                            "events": [
+                                {
+                                    "type": "Normal",
+                                    "reason": "Scheduled",
+                                    "count": 1,
+                                    "lastTimestamp": "2024-11-19T09:45:05+00:00",
+                                    "source": "default-scheduler"
+                                },
+                                {
+                                    "type": "Warning",
+                                    "reason": "BackOff",
+                                    "count": 4,
+                                    "lastTimestamp": "2024-11-19T09:46:30+00:00",
+                                    "source": "kubelet"
+                                }
+                            ],
+                            "podConditions": [
+                                {
+                                    "type": "PodScheduled",
+                                    "status": "True",
+                                    "reason": null
+                                },
+                                {
+                                    "type": "Ready",
+                                    "status": "False",
+                                    "reason": "ContainersNotReady"
+                                }
+                            ],
+                            "containerStatuses": [
+                                {
+                                    "name": "main",
+                                    "state": "WAITING",
+                                    "reason": "ImagePullBackOff",
+                                    "exitCode": null
+                                }
+                            ]
                         }
                     ]
                 }
@@ -1116,8 +3094,41 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn single_null_workflow_query() {
-        let workflow_name = "numpy-benchmark-pwtgn";
+    async fn task_pod_names() {
+        let workflow = serde_json::from_value::<IoArgoprojWorkflowV1alpha1Workflow>(json!({
+            "metadata": {
+                "name": "numpy-benchmark-wdkwj",
+                "annotations": {"workflows.argoproj.io/pod-name-format": "v2"}
+            },
+            "spec": {}
+        }))
+        .unwrap();
+        let node = |name: &str, template_name: &str| {
+            serde_json::from_value::<IoArgoprojWorkflowV1alpha1NodeStatus>(json!({
+                "id": "numpy-benchmark-wdkwj-3333639851",
+                "name": name,
+                "templateName": template_name,
+                "type": "Pod"
+            }))
+            .unwrap()
+        };
+
+        assert_eq!(
+            task_pod_name(&workflow, &node("numpy-benchmark-wdkwj", "numpy-test")),
+            "numpy-benchmark-wdkwj"
+        );
+        assert_eq!(
+            task_pod_name(
+                &workflow,
+                &node("numpy-benchmark-wdkwj.benchmark", "benchmark")
+            ),
+            "numpy-benchmark-wdkwj-benchmark-3333639851"
+        );
+    }
+
+    #[tokio::test]
+    async fn single_failed_workflow_query() {
+        let workflow_name = "numpy-benchmark-qhb59";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1127,7 +3138,7 @@ mod tests {
         let mut server = mockito::Server::new_async().await;
         let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-pwtgn-null.json");
+        response_file_path.push("get-workflow-qhb59-failed.json");
         let workflow_endpoint = server
             .mock(
                 "GET",
@@ -1149,7 +3160,14 @@ mod tests {
             query {{
                 workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
                     status {{
-                        __typename
+                        ...on WorkflowFailedStatus {{
+                            startTime
+                            endTime
+                            message
+                            tasks {{
+                                id
+                            }}
+                        }}
                     }}
                 }}
             }}
@@ -1161,35 +3179,38 @@ mod tests {
         workflow_endpoint.assert_async().await;
         let expected_data = json!({
             "workflow": {
-                "status": null,
+                "status": {
+                    "startTime": "2024-10-02T11:11:17+00:00",
+                    "endTime": "2024-10-02T11:12:32+00:00",
+                    "message": "OOMKilled (exit code 137)",
+                    "tasks": [{"id": workflow_name}]
+                }
             }
         });
         assert_eq!(resp.data.into_json().unwrap(), expected_data);
     }
 
     #[tokio::test]
-    async fn multiple_workflows_query() {
+    async fn single_errored_workflow_query() {
+        let workflow_name = "numpy-benchmark-jwcfp";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
             number: 1,
         };
-        let limit = 2;
 
         let mut server = mockito::Server::new_async().await;
-        let mut multiple_workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        multiple_workflows_response_file_path.push("test-assets");
-        multiple_workflows_response_file_path.push("get-workflows.json");
-
-        let workflows_endpoint = server
-            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
-            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
-                "listOptions.limit".to_string(),
-                limit.to_string(),
-            )]))
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-jwcfp-errored.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body_from_file(multiple_workflows_response_file_path)
+            .with_body_from_file(response_file_path)
             .create_async()
             .await;
 
@@ -1201,89 +3222,129 @@ mod tests {
         let query = format!(
             r#"
             query {{
-                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}, limit: {}) {{
-                    nodes {{
-                        name
-                    }}
-                    pageInfo {{
-                        endCursor
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        ...on WorkflowErroredStatus {{
+                            startTime
+                            endTime
+                            message
+                            tasks {{
+                                id
+                            }}
+                        }}
                     }}
                 }}
             }}
         "#,
-            visit.proposal_code, visit.proposal_number, visit.number, limit
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
         );
         let resp = schema.execute(query).await.into_result().unwrap();
 
-        workflows_endpoint.assert_async().await;
+        workflow_endpoint.assert_async().await;
         let expected_data = json!({
-            "workflows": {
-                "nodes": [
-                    {
-                        "name": "numpy-benchmark-wdkwj"
-                    },
-                    {
-                        "name": "numpy-benchmark-n6jsg"
-                    }
-                ],
-                "pageInfo": {"endCursor": "Mg"}
+            "workflow": {
+                "status": {
+                    "startTime": "2024-10-11T14:35:37+00:00",
+                    "endTime": "2024-10-11T14:35:37+00:00",
+                    "message": "error in entry template execution: Error applying PodSpecPatch",
+                    "tasks": [{"id": workflow_name}]
+                }
             }
         });
         assert_eq!(resp.data.into_json().unwrap(), expected_data);
     }
 
     #[tokio::test]
-    async fn multiple_succeeded_workflows_task_ids_query() {
-        let workflow_names = ["numpy-benchmark-wdkwj", "numpy-benchmark-n6jsg"];
-        let visit = Visit {
+    async fn single_running_workflow_query() {
+        let workflow_name = "numpy-benchmark-kc7pf";
+        let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
             number: 1,
         };
-        let limit = 2;
 
         let mut server = mockito::Server::new_async().await;
-        let mut multiple_workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        multiple_workflows_response_file_path.push("test-assets");
-        multiple_workflows_response_file_path.push("get-workflows.json");
-        let mut workflow_one_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        workflow_one_response_file_path.push("test-assets");
-        workflow_one_response_file_path.push("get-workflow-wdkwj.json");
-        let mut workflow_two_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        workflow_two_response_file_path.push("test-assets");
-        workflow_two_response_file_path.push("get-workflow-n6jsg.json");
-
-        let workflows_endpoint = server
-            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
-            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
-                "listOptions.limit".to_string(),
-                limit.to_string(),
-            )]))
-            .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body_from_file(multiple_workflows_response_file_path)
-            .create_async()
-            .await;
-
-        let workflow_one_endpoint = server
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-kc7pf-running.json");
+        let workflow_endpoint = server
             .mock(
                 "GET",
-                &format!("/api/v1/workflows/{}/{}", visit, workflow_names[0])[..],
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
             )
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body_from_file(workflow_one_response_file_path)
+            .with_body_from_file(response_file_path)
             .create_async()
             .await;
 
-        let workflow_two_endpoint = server
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        ...on WorkflowRunningStatus {{
+                            startTime
+                            message
+                            tasks {{
+                                id
+                                startTime
+                                endTime
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflow": {
+                "status": {
+                    "startTime": "2025-01-22T12:29:45+00:00",
+                    "message": null,
+                    "tasks": [
+                        {
+                            "id": workflow_name,
+                            "startTime": "2025-01-22T12:29:45+00:00",
+                            "endTime": null
+                        }
+                    ]
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn single_null_workflow_query() {
+        let workflow_name = "numpy-benchmark-pwtgn";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-pwtgn-null.json");
+        let workflow_endpoint = server
             .mock(
                 "GET",
-                &format!("/api/v1/workflows/{}/{}", visit, workflow_names[1])[..],
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
             )
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body_from_file(workflow_two_response_file_path)
+            .with_body_from_file(response_file_path)
             .create_async()
             .await;
 
@@ -1295,53 +3356,34 @@ mod tests {
         let query = format!(
             r#"
             query {{
-                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}, limit: {}){{
-                    nodes {{
-                        status {{
-                            ...on WorkflowSucceededStatus {{
-                                tasks {{
-                                    id
-                                }}
-                            }}
-                        }}
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        __typename
                     }}
                 }}
             }}
         "#,
-            visit.proposal_code, visit.proposal_number, visit.number, limit
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
         );
         let resp = schema.execute(query).await.into_result().unwrap();
 
-        workflows_endpoint.assert_async().await;
-        workflow_one_endpoint.assert_async().await;
-        workflow_two_endpoint.assert_async().await;
-
-        let nodes = workflow_names
-            .iter()
-            .map(|name| {
-                json!({
-                    "status": {
-                        "tasks": [{"id": name}],
-                    }
-                })
-            })
-            .collect::<Vec<_>>();
+        workflow_endpoint.assert_async().await;
         let expected_data = json!({
-            "workflows": {
-                "nodes": nodes,
+            "workflow": {
+                "status": null,
             }
         });
         assert_eq!(resp.data.into_json().unwrap(), expected_data);
     }
 
     #[tokio::test]
-    async fn multiple_workflows_query_default_limit() {
+    async fn multiple_workflows_query() {
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
             number: 1,
         };
-        let expected_default_limit = 10;
+        let limit = 2;
 
         let mut server = mockito::Server::new_async().await;
         let mut multiple_workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -1352,7 +3394,7 @@ mod tests {
             .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
             .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
                 "listOptions.limit".to_string(),
-                expected_default_limit.to_string(),
+                limit.to_string(),
             )]))
             .with_status(200)
             .with_header("content-type", "application/json")
@@ -1368,22 +3410,40 @@ mod tests {
         let query = format!(
             r#"
             query {{
-                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}, limit: {}) {{
                     nodes {{
                         name
                     }}
+                    pageInfo {{
+                        endCursor
+                    }}
                 }}
             }}
         "#,
-            visit.proposal_code, visit.proposal_number, visit.number
+            visit.proposal_code, visit.proposal_number, visit.number, limit
         );
-        schema.execute(query).await.into_result().unwrap();
+        let resp = schema.execute(query).await.into_result().unwrap();
+
         workflows_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflows": {
+                "nodes": [
+                    {
+                        "name": "numpy-benchmark-wdkwj"
+                    },
+                    {
+                        "name": "numpy-benchmark-n6jsg"
+                    }
+                ],
+                "pageInfo": {"endCursor": "Mg"}
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
     }
 
     #[tokio::test]
-    async fn get_artifacts_of_succeeded_workflow_query() {
-        let workflow_name = "numpy-benchmark-wdkwj";
+    async fn archived_workflow_query() {
+        let workflow_name = "numpy-benchmark-n6jsg";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1393,93 +3453,75 @@ mod tests {
         let mut server = mockito::Server::new_async().await;
         let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-wdkwj.json");
+        response_file_path.push("get-workflow-n6jsg.json");
+        let archived_workflow = serde_json::from_str::<serde_json::Value>(
+            &std::fs::read_to_string(response_file_path).unwrap(),
+        )
+        .unwrap();
         let workflow_endpoint = server
             .mock(
                 "GET",
                 &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
             )
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":5,"message":"workflows.argoproj.io \"numpy-benchmark-n6jsg\" not found"}"#)
+            .create_async()
+            .await;
+        let archive_endpoint = server
+            .mock("GET", "/api/v1/archived-workflows")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("namespace".to_string(), visit.to_string()),
+                mockito::Matcher::UrlEncoded(
+                    "listOptions.fieldSelector".to_string(),
+                    format!("metadata.name={workflow_name}"),
+                ),
+            ]))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body_from_file(response_file_path)
+            .with_body(json!({"metadata": {}, "items": [archived_workflow]}).to_string())
             .create_async()
             .await;
 
-        let s3_bucket = S3Bucket("test-bucket".to_string());
-        let s3_client_args = S3ClientArgs {
-            s3_endpoint_url: None,
-            s3_access_key_id: Some("test-access-key".to_string()),
-            s3_secret_access_key: Some("test-secret-key".to_string()),
-            s3_force_path_style: true,
-            s3_region: Some("us-west-2".to_string()),
-        };
-        let s3_client = Client::from(s3_client_args.clone());
-        let artifact_key = format!("{workflow_name}/{workflow_name}/main.log");
         let argo_server_url = Url::parse(&server.url()).unwrap();
         let schema = root_schema_builder()
             .data(ArgoServerUrl(argo_server_url))
             .data(test_token())
-            .data(s3_client)
-            .data(s3_bucket.clone())
             .finish();
-
         let query = format!(
             r#"
             query {{
                 workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    name
+                    archived
                     status {{
-                        ...on WorkflowSucceededStatus {{
-                            tasks {{
-                                artifacts {{
-                                    name
-                                    url
-                                }}
-                            }}
-                        }}
+                        __typename
                     }}
                 }}
             }}
         "#,
             workflow_name, visit.proposal_code, visit.proposal_number, visit.number
         );
-
-        let resp = schema
-            .execute(&query)
-            .await
-            .into_result()
-            .unwrap()
-            .data
-            .into_json()
-            .unwrap();
+        let resp = schema.execute(query).await.into_result().unwrap();
 
         workflow_endpoint.assert_async().await;
-
-        let actual_url = resp["workflow"]["status"]["tasks"][0]["artifacts"][0]["url"]
-            .as_str()
-            .expect("URL should be a string");
-        let expected_base = if s3_client_args.s3_force_path_style {
-            format!(
-                "https://s3.us-west-2.amazonaws.com/{}/{}",
-                s3_bucket.0, artifact_key
-            )
-        } else {
-            format!(
-                "https://{}.s3.us-west-2.amazonaws.com/{}",
-                s3_bucket.0, artifact_key
-            )
-        };
-        assert!(actual_url.starts_with(&expected_base),);
-        assert!(actual_url.contains("X-Amz-Algorithm=AWS4-HMAC-SHA256"),);
-        assert!(actual_url.contains("X-Amz-Expires=3600"),);
-        assert_eq!(
-            resp["workflow"]["status"]["tasks"][0]["artifacts"][0]["name"],
-            "main.log"
-        );
+        archive_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflow": {
+                "name": workflow_name,
+                "archived": true,
+                "status": {
+                    "__typename": "WorkflowSucceededStatus"
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
     }
 
     #[tokio::test]
-    async fn get_artifacts_mime_type_query() {
-        let workflow_name = "numpy-benchmark-wdkwj";
+    async fn archived_workflow_by_id_query() {
+        let workflow_name = "numpy-benchmark-n6jsg";
+        let uid = "d77cecff-078b-4a55-9f06-4429570e7f4a";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1489,12 +3531,25 @@ mod tests {
         let mut server = mockito::Server::new_async().await;
         let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-wdkwj.json");
+        response_file_path.push("get-workflow-n6jsg.json");
         let workflow_endpoint = server
             .mock(
                 "GET",
                 &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
             )
+            .match_query(mockito::Matcher::UrlEncoded(
+                "uid".to_string(),
+                uid.to_string(),
+            ))
+            .with_status(404)
+            .create_async()
+            .await;
+        let archive_endpoint = server
+            .mock("GET", &format!("/api/v1/archived-workflows/{uid}")[..])
+            .match_query(mockito::Matcher::UrlEncoded(
+                "namespace".to_string(),
+                visit.to_string(),
+            ))
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body_from_file(response_file_path)
@@ -1509,34 +3564,111 @@ mod tests {
         let query = format!(
             r#"
             query {{
-                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
-                    status {{
-                        ...on WorkflowSucceededStatus {{
-                            tasks {{
-                                artifacts {{
-                                    name
-                                    mimeType
-                                }}
-                            }}
-                        }}
-                    }}
+                workflowById(id: "{visit}:{workflow_name}:{uid}") {{
+                    id
+                    archived
                 }}
             }}
-        "#,
-            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        "#
         );
         let resp = schema.execute(query).await.into_result().unwrap();
 
         workflow_endpoint.assert_async().await;
+        archive_endpoint.assert_async().await;
         let expected_data = json!({
-            "workflow": {
-                "status": {
-                    "tasks": [{
-                        "artifacts": [{
-                            "name": "main.log",
-                            "mimeType": "text/plain"
-                        }]
-                    }]
+            "workflowById": {
+                "id": format!("{visit}:{workflow_name}:{uid}"),
+                "archived": true
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn multiple_workflows_including_archived_query() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+        let limit = 2;
+
+        let mut server = mockito::Server::new_async().await;
+        let mut workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        workflows_response_file_path.push("test-assets");
+        let mut archived_response_file_path = workflows_response_file_path.clone();
+        workflows_response_file_path.push("get-workflows.json");
+        archived_response_file_path.push("get-archived-workflows.json");
+
+        let workflows_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                (2 * limit + 1).to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                std::fs::read_to_string(&workflows_response_file_path)
+                    .unwrap()
+                    .replace(r#""continue": "2""#, r#""continue": null"#),
+            )
+            .create_async()
+            .await;
+        let archive_endpoint = server
+            .mock("GET", "/api/v1/archived-workflows")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("namespace".to_string(), visit.to_string()),
+                mockito::Matcher::UrlEncoded(
+                    "listOptions.limit".to_string(),
+                    (2 * limit + 1).to_string(),
+                ),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(archived_response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}, limit: {}, cursor: "Mg", includeArchived: true) {{
+                    nodes {{
+                        name
+                        archived
+                    }}
+                    pageInfo {{
+                        hasPreviousPage
+                        hasNextPage
+                        endCursor
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number, limit
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflows_endpoint.assert_async().await;
+        archive_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflows": {
+                "nodes": [
+                    {
+                        "name": "numpy-benchmark-x7k2p",
+                        "archived": true
+                    }
+                ],
+                "pageInfo": {
+                    "hasPreviousPage": true,
+                    "hasNextPage": false,
+                    "endCursor": "Mw"
                 }
             }
         });
@@ -1544,8 +3676,1977 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn workflow_template_ref() {
-        let workflow_name = "numpy-benchmark-wdkwj";
+    async fn multiple_workflows_sorted_and_filtered_query() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut multiple_workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        multiple_workflows_response_file_path.push("test-assets");
+        multiple_workflows_response_file_path.push("get-workflows.json");
+
+        let workflows_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded(
+                    "listOptions.labelSelector".to_string(),
+                    "workflows.argoproj.io/phase in (Succeeded)".to_string(),
+                ),
+                mockito::Matcher::UrlEncoded(
+                    "listOptions.limit".to_string(),
+                    PROXY_LIST_PAGE_SIZE.to_string(),
+                ),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(multiple_workflows_response_file_path)
+            .create_async()
+            .await;
+        let last_page_endpoint =
+            mock_last_workflows_page(&mut server, &format!("/api/v1/workflows/{visit}")).await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}, filter: {{workflowStatusFilter: {{succeeded: true}}, createdAfter: "2024-11-01T00:00:00Z", namePrefix: "numpy-benchmark-", sort: OLDEST_FIRST}}) {{
+                    nodes {{
+                        name
+                    }}
+                    pageInfo {{
+                        hasNextPage
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflows_endpoint.assert_async().await;
+        last_page_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflows": {
+                "nodes": [
+                    {
+                        "name": "numpy-benchmark-n6jsg"
+                    },
+                    {
+                        "name": "numpy-benchmark-wdkwj"
+                    }
+                ],
+                "pageInfo": {"hasNextPage": false}
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn filtered_workflows_are_read_past_the_first_upstream_page() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflows.json");
+        let listing = serde_json::from_str::<serde_json::Value>(
+            &std::fs::read_to_string(response_file_path).unwrap(),
+        )
+        .unwrap();
+        let mut first_page = listing.clone();
+        first_page["items"].as_array_mut().unwrap().truncate(1);
+        let mut last_page = listing;
+        last_page["items"].as_array_mut().unwrap().remove(0);
+        last_page["metadata"]["continue"] = serde_json::Value::Null;
+
+        let first_page_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                PROXY_LIST_PAGE_SIZE.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(first_page.to_string())
+            .create_async()
+            .await;
+        let last_page_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::UrlEncoded(
+                "listOptions.continue".to_string(),
+                "2".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(last_page.to_string())
+            .create_async()
+            .await;
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}, limit: 1, filter: {{namePrefix: "numpy-benchmark-n6"}}) {{
+                    nodes {{
+                        name
+                    }}
+                    pageInfo {{
+                        hasNextPage
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        first_page_endpoint.assert_async().await;
+        last_page_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflows": {
+                "nodes": [{"name": "numpy-benchmark-n6jsg"}],
+                "pageInfo": {"hasNextPage": false}
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn workflows_sorted_in_proxy_beyond_the_scan_limit_are_bad_input() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflows.json");
+        let workflows_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .expect(MAX_PROXY_SCANNED_WORKFLOWS / PROXY_LIST_PAGE_SIZE)
+            .create_async()
+            .await;
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}, filter: {{sort: LONGEST_FIRST}}) {{
+                    nodes {{
+                        name
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await;
+
+        workflows_endpoint.assert_async().await;
+        assert_eq!(resp.errors.len(), 1);
+        assert_eq!(
+            resp.errors[0]
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .cloned(),
+            Some(async_graphql::Value::from("BAD_INPUT"))
+        );
+    }
+
+    #[tokio::test]
+    async fn multiple_succeeded_workflows_task_ids_query() {
+        let workflow_names = ["numpy-benchmark-wdkwj", "numpy-benchmark-n6jsg"];
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+        let limit = 2;
+
+        let mut server = mockito::Server::new_async().await;
+        let mut multiple_workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        multiple_workflows_response_file_path.push("test-assets");
+        multiple_workflows_response_file_path.push("get-workflows.json");
+        let mut workflow_one_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        workflow_one_response_file_path.push("test-assets");
+        workflow_one_response_file_path.push("get-workflow-wdkwj.json");
+        let mut workflow_two_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        workflow_two_response_file_path.push("test-assets");
+        workflow_two_response_file_path.push("get-workflow-n6jsg.json");
+
+        let workflows_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                limit.to_string(),
+            )]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(multiple_workflows_response_file_path)
+            .create_async()
+            .await;
+
+        let workflow_one_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{}/{}", visit, workflow_names[0])[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(workflow_one_response_file_path)
+            .create_async()
+            .await;
+
+        let workflow_two_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{}/{}", visit, workflow_names[1])[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(workflow_two_response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}, limit: {}){{
+                    nodes {{
+                        status {{
+                            ...on WorkflowSucceededStatus {{
+                                tasks {{
+                                    id
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number, limit
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflows_endpoint.assert_async().await;
+        workflow_one_endpoint.assert_async().await;
+        workflow_two_endpoint.assert_async().await;
+
+        let nodes = workflow_names
+            .iter()
+            .map(|name| {
+                json!({
+                    "status": {
+                        "tasks": [{"id": name}],
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        let expected_data = json!({
+            "workflows": {
+                "nodes": nodes,
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn repeated_workflow_details_are_fetched_once_per_request() {
+        let workflow_names = ["numpy-benchmark-wdkwj", "numpy-benchmark-n6jsg"];
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+        let asset = |name| {
+            let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            path.push("test-assets");
+            path.push(name);
+            path
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let workflows_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflows.json"))
+            .expect(2)
+            .create_async()
+            .await;
+        let workflow_one_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{}/{}", visit, workflow_names[0])[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflow-wdkwj.json"))
+            .expect(1)
+            .create_async()
+            .await;
+        let workflow_two_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{}/{}", visit, workflow_names[1])[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflow-n6jsg.json"))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let argo_server_url = ArgoServerUrl(Url::parse(&server.url()).unwrap());
+        let loader_state = LoaderState {
+            argo_server_url: argo_server_url.clone(),
+            s3_client: Client::from(S3ClientArgs {
+                s3_endpoint_url: None,
+                s3_access_key_id: None,
+                s3_secret_access_key: None,
+                s3_force_path_style: true,
+                s3_region: None,
+            }),
+            s3_bucket: S3Bucket("test-bucket".to_string()),
+            artifact_metadata_cache: ArtifactMetadataCache::default(),
+            metrics_state: Arc::new(Metrics::new(&NoopMeterProvider::new())),
+        };
+        let schema = root_schema_builder()
+            .data(argo_server_url)
+            .data(loader_state.workflow_loader(&test_token()))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                first: workflows(visit: {{proposalCode: "{0}", proposalNumber: {1}, number: {2}}}) {{
+                    nodes {{
+                        status {{
+                            ...on WorkflowSucceededStatus {{
+                                tasks {{
+                                    id
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+                second: workflows(visit: {{proposalCode: "{0}", proposalNumber: {1}, number: {2}}}) {{
+                    nodes {{
+                        status {{
+                            ...on WorkflowSucceededStatus {{
+                                tasks {{
+                                    name
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number
+        );
+        schema.execute(query).await.into_result().unwrap();
+
+        workflows_endpoint.assert_async().await;
+        workflow_one_endpoint.assert_async().await;
+        workflow_two_endpoint.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn multiple_workflows_query_default_limit() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+        let expected_default_limit = 10;
+
+        let mut server = mockito::Server::new_async().await;
+        let mut multiple_workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        multiple_workflows_response_file_path.push("test-assets");
+        multiple_workflows_response_file_path.push("get-workflows.json");
+
+        let workflows_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                expected_default_limit.to_string(),
+            )]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(multiple_workflows_response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflows(visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    nodes {{
+                        name
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number
+        );
+        schema.execute(query).await.into_result().unwrap();
+        workflows_endpoint.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn get_artifacts_of_succeeded_workflow_query() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let s3_bucket = S3Bucket("test-bucket".to_string());
+        let s3_client_args = S3ClientArgs {
+            s3_endpoint_url: None,
+            s3_access_key_id: Some("test-access-key".to_string()),
+            s3_secret_access_key: Some("test-secret-key".to_string()),
+            s3_force_path_style: true,
+            s3_region: Some("us-west-2".to_string()),
+        };
+        let s3_client = Client::from(s3_client_args.clone());
+        let artifact_key = format!("{workflow_name}/{workflow_name}/main.log");
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .data(s3_client)
+            .data(s3_bucket.clone())
+            .finish();
+
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        ...on WorkflowSucceededStatus {{
+                            tasks {{
+                                artifacts {{
+                                    name
+                                    url
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+
+        let resp = schema
+            .execute(&query)
+            .await
+            .into_result()
+            .unwrap()
+            .data
+            .into_json()
+            .unwrap();
+
+        workflow_endpoint.assert_async().await;
+
+        let actual_url = resp["workflow"]["status"]["tasks"][0]["artifacts"][0]["url"]
+            .as_str()
+            .expect("URL should be a string");
+        let expected_base = if s3_client_args.s3_force_path_style {
+            format!(
+                "https://s3.us-west-2.amazonaws.com/{}/{}",
+                s3_bucket.0, artifact_key
+            )
+        } else {
+            format!(
+                "https://{}.s3.us-west-2.amazonaws.com/{}",
+                s3_bucket.0, artifact_key
+            )
+        };
+        assert!(actual_url.starts_with(&expected_base),);
+        assert!(actual_url.contains("X-Amz-Algorithm=AWS4-HMAC-SHA256"),);
+        assert!(actual_url.contains("X-Amz-Expires=3600"),);
+        assert_eq!(
+            resp["workflow"]["status"]["tasks"][0]["artifacts"][0]["name"],
+            "main.log"
+        );
+    }
+
+    #[tokio::test]
+    async fn workflow_progress_and_resources_query() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        ...on WorkflowSucceededStatus {{
+                            progress {{ completed total }}
+                            resourcesDuration
+                            estimatedDuration
+                            tasks {{
+                                progress {{ completed total }}
+                                resourcesDuration
+                                estimatedDuration
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+
+        let resp = schema.execute(&query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        let expected = json!({
+            "workflow": {
+                "status": {
+                    "progress": { "completed": 1, "total": 1 },
+                    "resourcesDuration": { "cpu": 6, "memory": 158 },
+                    "estimatedDuration": 68,
+                    "tasks": [{
+                        "progress": { "completed": 1, "total": 1 },
+                        "resourcesDuration": { "cpu": 6, "memory": 158 },
+                        "estimatedDuration": 59
+                    }]
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn task_inputs_and_outputs_query() {
+        let workflow_name = "numpy-benchmark-qhb59";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-qhb59-failed.json");
+        let mut workflow: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(response_file_path).unwrap()).unwrap();
+        workflow["status"]["outputs"] = json!({
+            "parameters": [{"name": "resolution", "value": "1.8"}]
+        });
+        let mut server = mockito::Server::new_async().await;
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(workflow.to_string())
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    outputs {{ parameters {{ name value }} }}
+                    status {{
+                        ...on WorkflowFailedStatus {{
+                            tasks {{
+                                inputs {{ parameters {{ name value }} }}
+                                outputs {{ parameters {{ name value }} exitCode result }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+
+        let resp = schema.execute(&query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        let expected = json!({
+            "workflow": {
+                "outputs": {
+                    "parameters": [{ "name": "resolution", "value": "1.8" }]
+                },
+                "status": {
+                    "tasks": [{
+                        "inputs": {
+                            "parameters": [
+                                { "name": "size", "value": "50000" },
+                                { "name": "memory", "value": "20Gi" }
+                            ]
+                        },
+                        "outputs": {
+                            "parameters": [],
+                            "exitCode": 137,
+                            "result": null
+                        }
+                    }]
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn get_proxied_artifact_url_query() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .data(ArtifactUrlArgs {
+                artifact_url_mode: ArtifactUrlMode::Proxied,
+                artifact_proxy_url: Some(
+                    Url::parse("https://workflows.diamond.ac.uk/graphql").unwrap(),
+                ),
+                ..Default::default()
+            })
+            .finish();
+
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        ...on WorkflowSucceededStatus {{
+                            tasks {{
+                                artifacts {{
+                                    url
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+
+        let resp = schema
+            .execute(&query)
+            .await
+            .into_result()
+            .unwrap()
+            .data
+            .into_json()
+            .unwrap();
+
+        workflow_endpoint.assert_async().await;
+        assert_eq!(
+            resp["workflow"]["status"]["tasks"][0]["artifacts"][0]["url"],
+            format!(
+                "https://workflows.diamond.ac.uk/graphql/artifacts/{visit}/{workflow_name}/{workflow_name}/main-logs"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn get_artifacts_mime_type_query() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        ...on WorkflowSucceededStatus {{
+                            tasks {{
+                                artifacts {{
+                                    name
+                                    mimeType
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflow": {
+                "status": {
+                    "tasks": [{
+                        "artifacts": [{
+                            "name": "main.log",
+                            "mimeType": "text/plain"
+                        }]
+                    }]
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case("main.log")]
+    #[case("plot.png")]
+    async fn get_artifact_preview_query(#[case] artifact_name: &str) {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                std::fs::read_to_string(response_file_path)
+                    .unwrap()
+                    .replace("main.log", artifact_name),
+            )
+            .create_async()
+            .await;
+
+        let mut image = Cursor::new(Vec::new());
+        image::RgbImage::new(512, 128)
+            .write_to(&mut image, image::ImageFormat::Png)
+            .unwrap();
+        let (range, object) = match artifact_name {
+            "main.log" => (
+                mockito::Matcher::Exact("bytes=0-16".to_string()),
+                b"Benchmarking numpy".to_vec(),
+            ),
+            _ => (mockito::Matcher::Missing, image.into_inner()),
+        };
+        let artifact_endpoint = server
+            .mock(
+                "GET",
+                &format!("/test-bucket/{workflow_name}/{workflow_name}/{artifact_name}")[..],
+            )
+            .match_query(mockito::Matcher::Any)
+            .match_header("range", range)
+            .with_status(200)
+            .with_body(object)
+            .create_async()
+            .await;
+
+        let s3_client = Client::from(S3ClientArgs {
+            s3_endpoint_url: Some(Url::parse(&server.url()).unwrap()),
+            s3_access_key_id: Some("test-access-key".to_string()),
+            s3_secret_access_key: Some("test-secret-key".to_string()),
+            s3_force_path_style: true,
+            s3_region: Some("us-west-2".to_string()),
+        });
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .data(s3_client)
+            .data(S3Bucket("test-bucket".to_string()))
+            .data(ArtifactPreviewArgs {
+                artifact_preview_max_bytes: 16,
+                artifact_thumbnail_size: 256,
+            })
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        ...on WorkflowSucceededStatus {{
+                            tasks {{
+                                artifacts {{
+                                    preview {{
+                                        ...on TextArtifactPreview {{
+                                            content
+                                            truncated
+                                        }}
+                                        ...on ImageArtifactPreview {{
+                                            mimeType
+                                            width
+                                            height
+                                        }}
+                                    }}
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        artifact_endpoint.assert_async().await;
+        let expected_preview = match artifact_name {
+            "main.log" => json!({
+                "content": "Benchmarking num",
+                "truncated": true
+            }),
+            _ => json!({
+                "mimeType": "image/png",
+                "width": 256,
+                "height": 64
+            }),
+        };
+        let expected_data = json!({
+            "workflow": {
+                "status": {
+                    "tasks": [{
+                        "artifacts": [{
+                            "preview": expected_preview
+                        }]
+                    }]
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn get_artifact_metadata_query() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+        let artifact_endpoint = server
+            .mock(
+                "HEAD",
+                &format!("/test-bucket/{workflow_name}/{workflow_name}/main.log")[..],
+            )
+            .with_status(200)
+            .with_header("last-modified", "Tue, 19 Nov 2024 09:46:59 GMT")
+            .with_header("etag", r#""9b2cf535f27731c974343645a3985328""#)
+            .with_header("x-amz-meta-instrument", "i03")
+            .with_header("content-length", "4294967296")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let s3_client = Client::from(S3ClientArgs {
+            s3_endpoint_url: Some(Url::parse(&server.url()).unwrap()),
+            s3_access_key_id: Some("test-access-key".to_string()),
+            s3_secret_access_key: Some("test-secret-key".to_string()),
+            s3_force_path_style: true,
+            s3_region: Some("us-west-2".to_string()),
+        });
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .data(s3_client)
+            .data(S3Bucket("test-bucket".to_string()))
+            .data(ArtifactMetadataCache::default())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    status {{
+                        ...on WorkflowSucceededStatus {{
+                            tasks {{
+                                artifacts {{
+                                    size
+                                    lastModified
+                                    etag
+                                    metadata
+                                }}
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        artifact_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflow": {
+                "status": {
+                    "tasks": [{
+                        "artifacts": [{
+                            "size": 4294967296_i64,
+                            "lastModified": "2024-11-19T09:46:59+00:00",
+                            "etag": "\"9b2cf535f27731c974343645a3985328\"",
+                            "metadata": {"instrument": "i03"}
+                        }]
+                    }]
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn workflow_template_ref() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                    templateRef
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflow": {
+                "templateRef": "numpy-benchmark"
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn multiple_workflows_query_with_filter() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        workflows_response_file_path.push("test-assets");
+        workflows_response_file_path.push("get-workflows.json");
+
+        let workflows_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
+                "listOptions.labelSelector".to_string(),
+                "workflows.argoproj.io/phase in (Error),workflows.argoproj.io/creator-preferred-username=enu43627".to_string(),
+            )]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(&workflows_response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url.clone()))
+            .data(test_token())
+            .finish();
+
+        let query = format!(
+            r#"
+            query {{
+                workflows(
+                    visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}},
+                    filter: {{ workflowStatusFilter: {{ error: true }}, creator: "enu43627" }}
+                ) {{
+                    nodes {{
+                        name
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number,
+        );
+        schema.execute(query).await.into_result().unwrap();
+        workflows_endpoint.assert_async().await;
+
+        let workflows_endpoint2 = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
+                "listOptions.labelSelector".to_string(),
+                "workflows.argoproj.io/phase in (Failed, Error)".to_string(),
+            )]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(workflows_response_file_path)
+            .create_async()
+            .await;
+
+        let query2 = format!(
+            r#"
+            query {{
+                workflows(
+                    visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}},
+                    filter: {{ workflowStatusFilter: {{ failed: true, error: true, succeeded: false, pending: false, running: false }} }}
+                ) {{
+                    nodes {{
+                        name
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number
+        );
+        schema.execute(query2).await.into_result().unwrap();
+        workflows_endpoint2.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn no_workflows_response() {
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        workflows_response_file_path.push("test-assets");
+        workflows_response_file_path.push("get-workflows-null.json");
+
+        let workflows_endpoint = server
+            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
+            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
+                "listOptions.labelSelector".to_string(),
+                "workflows.argoproj.io/creator-preferred-username=abc12345".to_string(),
+            )]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(&workflows_response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url.clone()))
+            .data(test_token())
+            .finish();
+
+        let query = format!(
+            r#"
+            query {{
+                workflows(
+                    visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}},
+                    filter: {{ creator: "abc12345" }}
+                ) {{
+                    nodes {{
+                        name
+                    }}
+                }}
+            }}
+        "#,
+            visit.proposal_code, visit.proposal_number, visit.number,
+        );
+
+        let expected = json!({
+            "workflows": {
+              "nodes": []
+            }
+        });
+
+        let response = schema.execute(query).await.into_result().unwrap();
+        workflows_endpoint.assert_async().await;
+        assert_eq!(response.data.into_json().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn workflow_parameters() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                   parameters
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflow": {
+                "parameters": {
+                    "memory": "10Gi",
+                    "size": 1000
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn workflow_array_parameters() {
+        let workflow_name = "xrf-tomography-chbrh";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-with-arrays.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                   parameters
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflow": {
+                "parameters": {
+                    "multiScan": [
+                        {
+                        "multiScan": {
+                            "end": 100,
+                            "excluded": [],
+                            "start": 10
+                        }
+                        }
+                    ],
+                    "outputFolder": "testing",
+                    "multiEdge": [
+                        {
+                        "edgeElement": "N",
+                        "edgeTransition": "Lb"
+                        },
+
+                    ],
+                    "normalise": true,
+                    "alignmentSection": "all",
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn workflow_creator() {
+        let workflow_name = "numpy-benchmark-wdkwj";
+        let visit = Visit {
+            proposal_code: "mg".to_string(),
+            proposal_number: 36964,
+            number: 1,
+        };
+
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-wdkwj.json");
+        let workflow_endpoint = server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let argo_server_url = Url::parse(&server.url()).unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(argo_server_url))
+            .data(test_token())
+            .finish();
+        let query = format!(
+            r#"
+            query {{
+                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
+                   creator {{ creatorId }}
+                }}
+            }}
+        "#,
+            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        );
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflow_endpoint.assert_async().await;
+        let expected_data = json!({
+            "workflow": {
+                "creator": {
+                    "creatorId": "enu43627",
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn my_workflows_query_across_visits() {
+        let creator_id = "ed66f621-7b56-4157-a255-a03af5bd8f0e";
+        let token = encode(
+            &Header::default(),
+            &json!({"sub": creator_id, "preferred_username": "enu43627"}),
+            &EncodingKey::from_secret(b"test-secret"),
+        )
+        .unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let _kubeconfig = TestKubeconfig::new(&server.url()).await.unwrap();
+
+        let asset = |name| {
+            let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            path.push("test-assets");
+            path.push(name);
+            path
+        };
+        let config_maps_endpoint = server
+            .mock("GET", "/api/v1/configmaps")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded(
+                    "labelSelector".to_string(),
+                    "app.kubernetes.io/managed-by=sessionspaces".to_string(),
+                ),
+                mockito::Matcher::UrlEncoded(
+                    "fieldSelector".to_string(),
+                    "metadata.name=sessionspaces".to_string(),
+                ),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("list-sessionspaces-configmaps.json"))
+            .create_async()
+            .await;
+        let label_selector = mockito::Matcher::UrlEncoded(
+            "listOptions.labelSelector".to_string(),
+            format!("workflows.argoproj.io/creator={creator_id}"),
+        );
+        let first_visit_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1")
+            .match_query(label_selector.clone())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflows.json"))
+            .create_async()
+            .await;
+        let first_visit_last_page_endpoint =
+            mock_last_workflows_page(&mut server, "/api/v1/workflows/mg36964-1").await;
+        let second_visit_endpoint = server
+            .mock("GET", "/api/v1/workflows/cm37235-3")
+            .match_query(label_selector)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflows-null.json"))
+            .create_async()
+            .await;
+        let other_visit_endpoint = server
+            .mock("GET", "/api/v1/workflows/sw12345-1")
+            .match_query(mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(KubernetesApiUrl(server.url().parse().unwrap()))
+            .data(ValidatedAuthToken::Valid(
+                Authorization::bearer(&token).unwrap(),
+            ))
+            .finish();
+        let query = r#"
+            query {
+                myWorkflows(sort: OLDEST_FIRST, limit: 1) {
+                    nodes {
+                        name
+                        visit {
+                            proposalCode
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }
+        "#;
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        config_maps_endpoint.assert_async().await;
+        first_visit_endpoint.assert_async().await;
+        first_visit_last_page_endpoint.assert_async().await;
+        second_visit_endpoint.assert_async().await;
+        other_visit_endpoint.assert_async().await;
+        let expected_data = json!({
+            "myWorkflows": {
+                "nodes": [
+                    {
+                        "name": "numpy-benchmark-n6jsg",
+                        "visit": {
+                            "proposalCode": "mg"
+                        }
+                    }
+                ],
+                "pageInfo": {
+                    "hasNextPage": true,
+                    "endCursor": "MQ"
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn my_workflows_query_from_visit_cache() {
+        use crate::graphql::VisitCache;
+
+        let creator_id = "ed66f621-7b56-4157-a255-a03af5bd8f0e";
+        let token = encode(
+            &Header::default(),
+            &json!({"sub": creator_id, "preferred_username": "enu43627"}),
+            &EncodingKey::from_secret(b"test-secret"),
+        )
+        .unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let _kubeconfig = TestKubeconfig::new(&server.url()).await.unwrap();
+
+        let asset = |name| {
+            let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            path.push("test-assets");
+            path.push(name);
+            path
+        };
+        let config_maps_endpoint = server
+            .mock("GET", "/api/v1/configmaps")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("list-sessionspaces-configmaps.json"))
+            .expect(1)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/configmaps")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "watch".to_string(),
+                "true".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .create_async()
+            .await;
+        let page_options = mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded(
+                "listOptions.labelSelector".to_string(),
+                format!("workflows.argoproj.io/creator={creator_id}"),
+            ),
+            mockito::Matcher::UrlEncoded("listOptions.limit".to_string(), "2".to_string()),
+        ]);
+        let first_visit_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1")
+            .match_query(page_options.clone())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflows.json"))
+            .create_async()
+            .await;
+        let second_visit_endpoint = server
+            .mock("GET", "/api/v1/workflows/cm37235-3")
+            .match_query(page_options)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflows-null.json"))
+            .create_async()
+            .await;
+
+        let visit_cache = VisitCache::spawn(&server.url().parse().unwrap())
+            .await
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while !visit_cache.is_ready() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(KubernetesApiUrl(server.url().parse().unwrap()))
+            .data(visit_cache)
+            .data(ValidatedAuthToken::Valid(
+                Authorization::bearer(&token).unwrap(),
+            ))
+            .finish();
+        let query = r#"
+            query {
+                myWorkflows(limit: 1) {
+                    nodes {
+                        name
+                    }
+                    pageInfo {
+                        hasNextPage
+                    }
+                }
+            }
+        "#;
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        config_maps_endpoint.assert_async().await;
+        first_visit_endpoint.assert_async().await;
+        second_visit_endpoint.assert_async().await;
+        let expected_data = json!({
+            "myWorkflows": {
+                "nodes": [{"name": "numpy-benchmark-wdkwj"}],
+                "pageInfo": {"hasNextPage": true}
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    fn role_args() -> RoleArgs {
+        RoleArgs {
+            role_claim: "groups".to_string(),
+            beamline_staff_role_pattern: Some(
+                regex::Regex::new("^(?<instrument>[a-z][0-9]+)_staff$").unwrap(),
+            ),
+            science_group_admin_role_pattern: Some(
+                regex::Regex::new("^(?<science_group>[a-z]+)_admin$").unwrap(),
+            ),
+            science_group_instruments: vec!["mx=i03".parse().unwrap()],
+            platform_admin_roles: vec!["workflows_admin".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn role_token(groups: &[&str]) -> ValidatedAuthToken {
+        let token = encode(
+            &Header::default(),
+            &json!({"sub": "test-subject", "groups": groups}),
+            &EncodingKey::from_secret(b"test-secret"),
+        )
+        .unwrap();
+        ValidatedAuthToken::Valid(Authorization::bearer(&token).unwrap())
+    }
+
+    #[tokio::test]
+    async fn instrument_workflows_query_across_visits() {
+        let mut server = mockito::Server::new_async().await;
+        let _kubeconfig = TestKubeconfig::new(&server.url()).await.unwrap();
+
+        let asset = |name| {
+            let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            path.push("test-assets");
+            path.push(name);
+            path
+        };
+        let config_maps_endpoint = server
+            .mock("GET", "/api/v1/configmaps")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("list-sessionspaces-configmaps.json"))
+            .create_async()
+            .await;
+        let first_visit_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflows.json"))
+            .create_async()
+            .await;
+        let first_visit_last_page_endpoint =
+            mock_last_workflows_page(&mut server, "/api/v1/workflows/mg36964-1").await;
+        let second_visit_endpoint = server
+            .mock("GET", "/api/v1/workflows/cm37235-3")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(asset("get-workflows-null.json"))
+            .create_async()
+            .await;
+        let other_instrument_endpoint = server
+            .mock("GET", "/api/v1/workflows/sw12345-1")
+            .match_query(mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(KubernetesApiUrl(server.url().parse().unwrap()))
+            .data(role_args())
+            .data(role_token(&["i03_staff"]))
+            .finish();
+        let query = r#"
+            query {
+                instrumentWorkflows(instrument: "i03") {
+                    nodes {
+                        name
+                    }
+                }
+            }
+        "#;
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        config_maps_endpoint.assert_async().await;
+        first_visit_endpoint.assert_async().await;
+        first_visit_last_page_endpoint.assert_async().await;
+        second_visit_endpoint.assert_async().await;
+        other_instrument_endpoint.assert_async().await;
+        let expected_data = json!({
+            "instrumentWorkflows": {
+                "nodes": [
+                    {"name": "numpy-benchmark-wdkwj"},
+                    {"name": "numpy-benchmark-n6jsg"}
+                ]
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case("instrumentWorkflows(instrument: \"i03\")", &["i04_staff"])]
+    #[case("instrumentWorkflows(instrument: \"i03\")", &[])]
+    #[case("instrumentWorkflows(instrument: \"i04\")", &["mx_admin"])]
+    #[case("allWorkflows", &["i03_staff"])]
+    #[case("allWorkflows", &["mx_admin"])]
+    async fn workflows_query_without_role_is_forbidden(
+        #[case] field: &str,
+        #[case] groups: &[&str],
+    ) {
+        let server = mockito::Server::new_async().await;
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(role_args())
+            .data(role_token(groups))
+            .finish();
+        let query = format!("query {{ {field} {{ nodes {{ name }} }} }}");
+        let resp = schema.execute(query).await;
+
+        assert_eq!(resp.errors.len(), 1);
+        assert_eq!(
+            resp.errors[0]
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .cloned(),
+            Some(async_graphql::Value::from(
+                AuthErrorCode::Forbidden.to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn all_workflows_query_for_admins() {
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflows.json");
+        let workflows_endpoint = server
+            .mock("GET", "/api/v1/workflows/")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                "2".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await;
+
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(role_args())
+            .data(role_token(&["workflows_admin"]))
+            .finish();
+        let query = r#"
+            query {
+                allWorkflows(limit: 2) {
+                    nodes {
+                        name
+                        visit {
+                            proposalCode
+                            proposalNumber
+                            number
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                    }
+                }
+            }
+        "#;
+        let resp = schema.execute(query).await.into_result().unwrap();
+
+        workflows_endpoint.assert_async().await;
+        let visit = json!({"proposalCode": "mg", "proposalNumber": 36964, "number": 1});
+        let expected_data = json!({
+            "allWorkflows": {
+                "nodes": [
+                    {"name": "numpy-benchmark-wdkwj", "visit": visit},
+                    {"name": "numpy-benchmark-n6jsg", "visit": visit}
+                ],
+                "pageInfo": {
+                    "hasNextPage": true
+                }
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+    }
+
+    #[tokio::test]
+    async fn all_workflows_sorted_in_proxy_are_bounded_and_use_privileged_token() {
+        let mut server = mockito::Server::new_async().await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        let workflows_endpoint = server
+            .mock("GET", "/api/v1/workflows/")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "listOptions.limit".to_string(),
+                PROXY_LIST_PAGE_SIZE.to_string(),
+            ))
+            .match_header("authorization", "Bearer privileged-token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path.join("get-workflows.json"))
+            .create_async()
+            .await;
+        let last_page_endpoint = mock_last_workflows_page(&mut server, "/api/v1/workflows/").await;
+        let details_endpoint = server
+            .mock("GET", "/api/v1/workflows/mg36964-1/numpy-benchmark-n6jsg")
+            .match_header("authorization", "Bearer privileged-token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path.join("get-workflow-n6jsg.json"))
+            .create_async()
+            .await;
+
+        let token_file = std::env::temp_dir().join(format!(
+            "graph-proxy-privileged-token-{}",
+            std::process::id()
+        ));
+        std::fs::write(&token_file, "privileged-token\n").unwrap();
+        let schema = root_schema_builder()
+            .data(ArgoServerUrl(Url::parse(&server.url()).unwrap()))
+            .data(RoleArgs {
+                privileged_token_file: Some(token_file.clone()),
+                ..role_args()
+            })
+            .data(role_token(&["workflows_admin"]))
+            .finish();
+        let query = r#"
+            query {
+                allWorkflows(limit: 1, filter: {sort: OLDEST_FIRST}) {
+                    nodes {
+                        name
+                        status {
+                            ... on WorkflowSucceededStatus {
+                                tasks {
+                                    name
+                                }
+                            }
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                    }
+                }
+            }
+        "#;
+        let resp = schema.execute(query).await.into_result().unwrap();
+        std::fs::remove_file(token_file).unwrap();
+
+        workflows_endpoint.assert_async().await;
+        last_page_endpoint.assert_async().await;
+        details_endpoint.assert_async().await;
+        let data = resp.data.into_json().unwrap();
+        assert_eq!(
+            data["allWorkflows"]["nodes"][0]["name"],
+            "numpy-benchmark-n6jsg"
+        );
+        assert!(!data["allWorkflows"]["nodes"][0]["status"]["tasks"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+        assert_eq!(data["allWorkflows"]["pageInfo"]["hasNextPage"], true);
+    }
+
+    /// Mock the Argo Server returning the empty last page continuing the listing in
+    /// `get-workflows.json`
+    async fn mock_last_workflows_page(
+        server: &mut mockito::ServerGuard,
+        path: &str,
+    ) -> mockito::Mock {
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflows-null.json");
+        server
+            .mock("GET", path)
+            .match_query(mockito::Matcher::UrlEncoded(
+                "listOptions.continue".to_string(),
+                "2".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await
+    }
+
+    /// Mock the Argo Server returning the workflow currently holding a name
+    async fn mock_current_workflow(
+        server: &mut mockito::ServerGuard,
+        visit: &Visit,
+        workflow_name: &str,
+        fixture: &str,
+    ) -> mockito::Mock {
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push(fixture);
+        server
+            .mock(
+                "GET",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_file(response_file_path)
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn stop_workflow_mutation() {
+        let workflow_name = "numpy-benchmark-kc7pf";
+        let workflow_uid = "9aa7ef6e-3e9a-4fdb-a5a2-6125f6a98fca";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1555,12 +5656,24 @@ mod tests {
         let mut server = mockito::Server::new_async().await;
         let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-wdkwj.json");
-        let workflow_endpoint = server
+        response_file_path.push("get-workflow-kc7pf-running.json");
+        let workflow_endpoint = mock_current_workflow(
+            &mut server,
+            &visit,
+            workflow_name,
+            "get-workflow-kc7pf-running.json",
+        )
+        .await;
+        let stop_endpoint = server
             .mock(
-                "GET",
-                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+                "PUT",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}/stop")[..],
             )
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "name": workflow_name,
+                "namespace": visit.to_string(),
+                "message": "No longer needed",
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body_from_file(response_file_path)
@@ -1574,27 +5687,29 @@ mod tests {
             .finish();
         let query = format!(
             r#"
-            query {{
-                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
-                    templateRef
+            mutation {{
+                stopWorkflow(id: "{visit}:{workflow_name}:{workflow_uid}", message: "No longer needed") {{
+                    name
                 }}
             }}
-        "#,
-            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        "#
         );
         let resp = schema.execute(query).await.into_result().unwrap();
 
         workflow_endpoint.assert_async().await;
+        stop_endpoint.assert_async().await;
         let expected_data = json!({
-            "workflow": {
-                "templateRef": "numpy-benchmark"
+            "stopWorkflow": {
+                "name": workflow_name
             }
         });
         assert_eq!(resp.data.into_json().unwrap(), expected_data);
     }
 
     #[tokio::test]
-    async fn multiple_workflows_query_with_filter() {
+    async fn retry_workflow_mutation_with_node_field_selector() {
+        let workflow_name = "numpy-benchmark-kc7pf";
+        let workflow_uid = "9aa7ef6e-3e9a-4fdb-a5a2-6125f6a98fca";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1602,79 +5717,77 @@ mod tests {
         };
 
         let mut server = mockito::Server::new_async().await;
-        let mut workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        workflows_response_file_path.push("test-assets");
-        workflows_response_file_path.push("get-workflows.json");
-
-        let workflows_endpoint = server
-            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
-            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
-                "listOptions.labelSelector".to_string(),
-                "workflows.argoproj.io/phase in (Error),workflows.argoproj.io/creator-preferred-username=enu43627".to_string(),
-            )]))
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-kc7pf-running.json");
+        let workflow_endpoint = mock_current_workflow(
+            &mut server,
+            &visit,
+            workflow_name,
+            "get-workflow-kc7pf-running.json",
+        )
+        .await;
+        let retry_endpoint = server
+            .mock(
+                "PUT",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}/retry")[..],
+            )
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "name": workflow_name,
+                "namespace": visit.to_string(),
+                "nodeFieldSelector": "displayName=numpy-test",
+                "restartSuccessful": true,
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body_from_file(&workflows_response_file_path)
+            .with_body_from_file(response_file_path)
             .create_async()
             .await;
 
         let argo_server_url = Url::parse(&server.url()).unwrap();
         let schema = root_schema_builder()
-            .data(ArgoServerUrl(argo_server_url.clone()))
+            .data(ArgoServerUrl(argo_server_url))
             .data(test_token())
             .finish();
-
+        let expected_id = format!("{visit}:{workflow_name}:{workflow_uid}");
         let query = format!(
             r#"
-            query {{
-                workflows(
-                    visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}},
-                    filter: {{ workflowStatusFilter: {{ error: true }}, creator: "enu43627" }}
-                ) {{
-                    nodes {{
-                        name
-                    }}
+            mutation {{
+                retryWorkflow(id: "{expected_id}", nodeFieldSelector: "displayName=numpy-test", restartSuccessful: true) {{
+                    id
                 }}
             }}
-        "#,
-            visit.proposal_code, visit.proposal_number, visit.number,
+        "#
         );
-        schema.execute(query).await.into_result().unwrap();
-        workflows_endpoint.assert_async().await;
-
-        let workflows_endpoint2 = server
-            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
-            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
-                "listOptions.labelSelector".to_string(),
-                "workflows.argoproj.io/phase in (Failed, Error)".to_string(),
-            )]))
-            .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body_from_file(workflows_response_file_path)
-            .create_async()
-            .await;
+        let resp = schema.execute(query).await.into_result().unwrap();
 
-        let query2 = format!(
-            r#"
-            query {{
-                workflows(
-                    visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}},
-                    filter: {{ workflowStatusFilter: {{ failed: true, error: true, succeeded: false, pending: false, running: false }} }}
-                ) {{
-                    nodes {{
-                        name
-                    }}
-                }}
-            }}
-        "#,
-            visit.proposal_code, visit.proposal_number, visit.number
-        );
-        schema.execute(query2).await.into_result().unwrap();
-        workflows_endpoint2.assert_async().await;
+        workflow_endpoint.assert_async().await;
+        retry_endpoint.assert_async().await;
+        let expected_data = json!({
+            "retryWorkflow": {
+                "id": expected_id
+            }
+        });
+        assert_eq!(resp.data.into_json().unwrap(), expected_data);
     }
 
     #[tokio::test]
-    async fn no_workflows_response() {
+    #[rstest]
+    #[case("terminateWorkflow", "terminate", "")]
+    #[case("resubmitWorkflow", "resubmit", ", memoized: true")]
+    #[case("suspendWorkflow", "suspend", "")]
+    #[case(
+        "resumeWorkflow",
+        "resume",
+        r#", nodeFieldSelector: "displayName=numpy-test""#
+    )]
+    async fn lifecycle_workflow_mutation(
+        #[case] mutation: &str,
+        #[case] action: &str,
+        #[case] arguments: &str,
+    ) {
+        let workflow_name = "numpy-benchmark-kc7pf";
+        let workflow_uid = "9aa7ef6e-3e9a-4fdb-a5a2-6125f6a98fca";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1682,58 +5795,58 @@ mod tests {
         };
 
         let mut server = mockito::Server::new_async().await;
-        let mut workflows_response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        workflows_response_file_path.push("test-assets");
-        workflows_response_file_path.push("get-workflows-null.json");
-
-        let workflows_endpoint = server
-            .mock("GET", &format!("/api/v1/workflows/{visit}")[..])
-            .match_query(mockito::Matcher::AllOf(vec![mockito::Matcher::UrlEncoded(
-                "listOptions.labelSelector".to_string(),
-                "workflows.argoproj.io/creator-preferred-username=abc12345".to_string(),
-            )]))
+        let workflow_endpoint = mock_current_workflow(
+            &mut server,
+            &visit,
+            workflow_name,
+            "get-workflow-kc7pf-running.json",
+        )
+        .await;
+        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        response_file_path.push("test-assets");
+        response_file_path.push("get-workflow-kc7pf-running.json");
+        let action_endpoint = server
+            .mock(
+                "PUT",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}/{action}")[..],
+            )
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "name": workflow_name,
+                "namespace": visit.to_string(),
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body_from_file(&workflows_response_file_path)
+            .with_body_from_file(response_file_path)
             .create_async()
             .await;
 
         let argo_server_url = Url::parse(&server.url()).unwrap();
         let schema = root_schema_builder()
-            .data(ArgoServerUrl(argo_server_url.clone()))
+            .data(ArgoServerUrl(argo_server_url))
             .data(test_token())
             .finish();
-
         let query = format!(
             r#"
-            query {{
-                workflows(
-                    visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}},
-                    filter: {{ creator: "abc12345" }}
-                ) {{
-                    nodes {{
-                        name
-                    }}
+            mutation {{
+                {mutation}(id: "{visit}:{workflow_name}:{workflow_uid}"{arguments}) {{
+                    name
                 }}
             }}
-        "#,
-            visit.proposal_code, visit.proposal_number, visit.number,
+        "#
         );
+        let resp = schema.execute(query).await.into_result().unwrap();
 
-        let expected = json!({
-            "workflows": {
-              "nodes": []
-            }
-        });
-
-        let response = schema.execute(query).await.into_result().unwrap();
-        workflows_endpoint.assert_async().await;
-        assert_eq!(response.data.into_json().unwrap(), expected);
+        workflow_endpoint.assert_async().await;
+        action_endpoint.assert_async().await;
+        assert_eq!(
+            resp.data.into_json().unwrap(),
+            json!({ mutation: { "name": workflow_name } })
+        );
     }
 
     #[tokio::test]
-    async fn workflow_parameters() {
-        let workflow_name = "numpy-benchmark-wdkwj";
+    async fn lifecycle_mutation_of_reused_name_is_not_found() {
+        let workflow_name = "numpy-benchmark-kc7pf";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1741,17 +5854,19 @@ mod tests {
         };
 
         let mut server = mockito::Server::new_async().await;
-        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-wdkwj.json");
-        let workflow_endpoint = server
+        let workflow_endpoint = mock_current_workflow(
+            &mut server,
+            &visit,
+            workflow_name,
+            "get-workflow-kc7pf-running.json",
+        )
+        .await;
+        let stop_endpoint = server
             .mock(
-                "GET",
-                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+                "PUT",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}/stop")[..],
             )
-            .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body_from_file(response_file_path)
+            .expect(0)
             .create_async()
             .await;
 
@@ -1762,31 +5877,32 @@ mod tests {
             .finish();
         let query = format!(
             r#"
-            query {{
-                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
-                   parameters
+            mutation {{
+                stopWorkflow(id: "{visit}:{workflow_name}:00000000-0000-0000-0000-000000000000") {{
+                    name
                 }}
             }}
-        "#,
-            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        "#
         );
-        let resp = schema.execute(query).await.into_result().unwrap();
+        let resp = schema.execute(query).await;
 
         workflow_endpoint.assert_async().await;
-        let expected_data = json!({
-            "workflow": {
-                "parameters": {
-                    "memory": "10Gi",
-                    "size": 1000
-                }
-            }
-        });
-        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+        stop_endpoint.assert_async().await;
+        assert_eq!(resp.errors.len(), 1);
+        assert_eq!(
+            resp.errors[0]
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .cloned(),
+            Some(async_graphql::Value::from("NOT_FOUND"))
+        );
     }
 
     #[tokio::test]
-    async fn workflow_array_parameters() {
-        let workflow_name = "xrf-tomography-chbrh";
+    async fn rejected_lifecycle_mutation_is_coded() {
+        let workflow_name = "numpy-benchmark-kc7pf";
+        let workflow_uid = "9aa7ef6e-3e9a-4fdb-a5a2-6125f6a98fca";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1794,17 +5910,21 @@ mod tests {
         };
 
         let mut server = mockito::Server::new_async().await;
-        let mut response_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        response_file_path.push("test-assets");
-        response_file_path.push("get-workflow-with-arrays.json");
-        let workflow_endpoint = server
+        mock_current_workflow(
+            &mut server,
+            &visit,
+            workflow_name,
+            "get-workflow-kc7pf-running.json",
+        )
+        .await;
+        server
             .mock(
-                "GET",
-                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+                "PUT",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}/suspend")[..],
             )
-            .with_status(200)
+            .with_status(403)
             .with_header("content-type", "application/json")
-            .with_body_from_file(response_file_path)
+            .with_body(r#"{"code":7,"message":"workflows.argoproj.io is forbidden"}"#)
             .create_async()
             .await;
 
@@ -1815,48 +5935,30 @@ mod tests {
             .finish();
         let query = format!(
             r#"
-            query {{
-                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
-                   parameters
+            mutation {{
+                suspendWorkflow(id: "{visit}:{workflow_name}:{workflow_uid}") {{
+                    name
                 }}
             }}
-        "#,
-            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        "#
         );
-        let resp = schema.execute(query).await.into_result().unwrap();
-
-        workflow_endpoint.assert_async().await;
-        let expected_data = json!({
-            "workflow": {
-                "parameters": {
-                    "multiScan": [
-                        {
-                        "multiScan": {
-                            "end": 100,
-                            "excluded": [],
-                            "start": 10
-                        }
-                        }
-                    ],
-                    "outputFolder": "testing",
-                    "multiEdge": [
-                        {
-                        "edgeElement": "N",
-                        "edgeTransition": "Lb"
-                        },
+        let resp = schema.execute(query).await;
 
-                    ],
-                    "normalise": true,
-                    "alignmentSection": "all",
-                }
-            }
-        });
-        assert_eq!(resp.data.into_json().unwrap(), expected_data);
+        assert_eq!(resp.errors.len(), 1);
+        assert_eq!(
+            resp.errors[0]
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .cloned(),
+            Some(async_graphql::Value::from("FORBIDDEN"))
+        );
     }
 
     #[tokio::test]
-    async fn workflow_creator() {
+    async fn delete_workflow_mutation() {
         let workflow_name = "numpy-benchmark-wdkwj";
+        let workflow_uid = "bed157b2-ecf2-4423-9945-8ecfa767a151";
         let visit = Visit {
             proposal_code: "mg".to_string(),
             proposal_number: 36964,
@@ -1872,11 +5974,29 @@ mod tests {
                 "GET",
                 &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
             )
+            .match_query(mockito::Matcher::UrlEncoded(
+                "uid".into(),
+                workflow_uid.into(),
+            ))
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body_from_file(response_file_path)
             .create_async()
             .await;
+        let delete_endpoint = server
+            .mock(
+                "DELETE",
+                &format!("/api/v1/workflows/{visit}/{workflow_name}")[..],
+            )
+            .match_query(mockito::Matcher::UrlEncoded(
+                "deleteOptions.preconditions.uid".into(),
+                workflow_uid.into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{}")
+            .create_async()
+            .await;
 
         let argo_server_url = Url::parse(&server.url()).unwrap();
         let schema = root_schema_builder()
@@ -1885,22 +6005,20 @@ mod tests {
             .finish();
         let query = format!(
             r#"
-            query {{
-                workflow(name: "{}", visit: {{proposalCode: "{}", proposalNumber: {}, number: {}}}) {{
-                   creator {{ creatorId }}
+            mutation {{
+                deleteWorkflow(id: "{visit}:{workflow_name}:{workflow_uid}") {{
+                    name
                 }}
             }}
-        "#,
-            workflow_name, visit.proposal_code, visit.proposal_number, visit.number
+        "#
         );
         let resp = schema.execute(query).await.into_result().unwrap();
 
         workflow_endpoint.assert_async().await;
+        delete_endpoint.assert_async().await;
         let expected_data = json!({
-            "workflow": {
-                "creator": {
-                    "creatorId": "enu43627",
-                }
+            "deleteWorkflow": {
+                "name": workflow_name
             }
         });
         assert_eq!(resp.data.into_json().unwrap(), expected_data);
@@ -0,0 +1,409 @@
+use crate::{
+    s3client::{Client as S3Client, S3Bucket},
+    ArgoServerUrl,
+};
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use jsonwebtoken::jwk::JwkSet;
+use kube::Client as KubernetesClient;
+use serde::Serialize;
+use std::{
+    future::Future,
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// The maximum time a single dependency check may take before it is reported as unavailable
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long the readiness of the dependencies is reused before they are checked again
+const READINESS_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// The upstream services checked for readiness
+#[derive(Clone)]
+pub struct HealthState {
+    /// The URL of the Argo Server
+    pub argo_server_url: ArgoServerUrl,
+    /// A client of the Kubernetes API, if one could be configured
+    pub kubernetes_client: Option<KubernetesClient>,
+    /// The client used to query the artifact store
+    pub s3_client: S3Client,
+    /// The bucket in which artifacts are stored
+    pub s3_bucket: S3Bucket,
+    /// The URL of the OIDC issuer's JSON Web Key Set
+    pub jwks_uri: String,
+    /// The most recently reported readiness
+    pub readiness_cache: ReadinessCache,
+}
+
+/// The most recently reported readiness and when it was checked, shared by concurrent probes
+#[derive(Debug, Clone, Default)]
+pub struct ReadinessCache(Arc<Mutex<Option<(Instant, Readiness)>>>);
+
+impl std::fmt::Debug for HealthState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthState")
+            .field("argo_server_url", &self.argo_server_url)
+            .field("kubernetes_client", &self.kubernetes_client.is_some())
+            .field("s3_bucket", &self.s3_bucket)
+            .field("jwks_uri", &self.jwks_uri)
+            .finish()
+    }
+}
+
+/// The status of a single dependency
+#[derive(Debug, Clone, Serialize)]
+struct DependencyStatus {
+    /// Whether the dependency could be reached
+    ok: bool,
+    /// The reason the dependency could not be reached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The readiness of the service and each of its dependencies
+#[derive(Debug, Clone, Serialize)]
+struct Readiness {
+    /// Whether every dependency required to serve requests could be reached
+    ok: bool,
+    /// The Argo Server
+    argo: DependencyStatus,
+    /// The Kubernetes API
+    kubernetes: DependencyStatus,
+    /// The artifact store, which only artifact downloads depend upon
+    s3: DependencyStatus,
+    /// The OIDC issuer's JSON Web Key Set, which is cached by the token validator
+    jwks: DependencyStatus,
+}
+
+/// Reports that the service is alive
+pub async fn liveness() -> impl IntoResponse {
+    StatusCode::OK
+}
+
+/// Reports whether the service can reach each of its dependencies
+///
+/// Only the Argo Server and the Kubernetes API are required for the service to be ready, as
+/// without them no request can be served. The artifact store and the JSON Web Key Set are reported
+/// but do not remove the service from rotation. Results are reused for [`READINESS_CACHE_TTL`] so
+/// frequent probes do not load the dependencies.
+pub async fn readiness(State(state): State<HealthState>) -> impl IntoResponse {
+    let mut cache = state.readiness_cache.0.lock().await;
+    let readiness = match cache.as_ref() {
+        Some((checked_at, readiness)) if checked_at.elapsed() < READINESS_CACHE_TTL => {
+            readiness.clone()
+        }
+        _ => {
+            let readiness = check_dependencies(&state).await;
+            *cache = Some((Instant::now(), readiness.clone()));
+            readiness
+        }
+    };
+    drop(cache);
+    let status = if readiness.ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness))
+}
+
+/// Checks each of the dependencies concurrently
+async fn check_dependencies(state: &HealthState) -> Readiness {
+    let (argo, kubernetes, s3, jwks) = tokio::join!(
+        check("argo", check_argo(&state.argo_server_url)),
+        check(
+            "kubernetes",
+            check_kubernetes(state.kubernetes_client.clone())
+        ),
+        check("s3", check_s3(&state.s3_client, &state.s3_bucket)),
+        check("jwks", check_jwks(&state.jwks_uri)),
+    );
+    Readiness {
+        ok: argo.ok && kubernetes.ok,
+        argo,
+        kubernetes,
+        s3,
+        jwks,
+    }
+}
+
+/// Runs a dependency check, bounded by the [`CHECK_TIMEOUT`]
+async fn check(
+    dependency: &str,
+    check: impl Future<Output = anyhow::Result<()>>,
+) -> DependencyStatus {
+    let result = tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("Timed out after {CHECK_TIMEOUT:?}")));
+    match result {
+        Ok(()) => DependencyStatus {
+            ok: true,
+            error: None,
+        },
+        Err(err) => {
+            warn!("Readiness check of {dependency} failed: {err:#}");
+            DependencyStatus {
+                ok: false,
+                error: Some(format!("{err:#}")),
+            }
+        }
+    }
+}
+
+/// Checks the Argo Server responds to unauthenticated version requests
+async fn check_argo(argo_server_url: &ArgoServerUrl) -> anyhow::Result<()> {
+    let mut url = argo_server_url.deref().clone();
+    url.path_segments_mut()
+        .unwrap()
+        .extend(["api", "v1", "version"]);
+    reqwest::get(url).await?.error_for_status()?;
+    Ok(())
+}
+
+/// Checks the Kubernetes API responds to version requests
+async fn check_kubernetes(client: Option<KubernetesClient>) -> anyhow::Result<()> {
+    let client = client.ok_or_else(|| anyhow::anyhow!("Kubernetes client is not configured"))?;
+    client.apiserver_version().await?;
+    Ok(())
+}
+
+/// Checks the artifact store responds to requests for objects in the artifact bucket
+///
+/// The service only needs `s3:GetObject`, which does not permit requests for the bucket itself,
+/// so a missing object is requested instead. Without `s3:ListBucket` missing objects are reported
+/// as forbidden, so either response shows the store is reachable.
+async fn check_s3(client: &S3Client, bucket: &S3Bucket) -> anyhow::Result<()> {
+    let result = client
+        .get_object()
+        .bucket(bucket.as_str())
+        .key(".graph-proxy-readiness")
+        .send()
+        .await;
+    match result {
+        Ok(_) => Ok(()),
+        Err(err)
+            if err
+                .raw_response()
+                .is_some_and(|response| matches!(response.status().as_u16(), 403 | 404)) =>
+        {
+            Ok(())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Checks the OIDC issuer serves a valid JSON Web Key Set
+async fn check_jwks(jwks_uri: &str) -> anyhow::Result<()> {
+    reqwest::get(jwks_uri)
+        .await?
+        .error_for_status()?
+        .json::<JwkSet>()
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{liveness, readiness, HealthState, ReadinessCache};
+    use crate::{
+        s3client::{Client, S3Bucket, S3ClientArgs},
+        ArgoServerUrl,
+    };
+    use axum::{
+        body::to_bytes,
+        extract::State,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+    use serde_json::{json, Value};
+    use url::Url;
+
+    fn test_state(server: &mockito::Server) -> HealthState {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        HealthState {
+            argo_server_url: ArgoServerUrl(Url::parse(&server.url()).unwrap()),
+            kubernetes_client: Some(
+                kube::Client::try_from(kube::Config::new(server.url().parse().unwrap())).unwrap(),
+            ),
+            s3_client: Client::from(S3ClientArgs {
+                s3_endpoint_url: Some(Url::parse(&server.url()).unwrap()),
+                s3_access_key_id: Some("test-access-key".to_string()),
+                s3_secret_access_key: Some("test-secret-key".to_string()),
+                s3_force_path_style: true,
+                s3_region: Some("us-west-2".to_string()),
+            }),
+            s3_bucket: S3Bucket("test-bucket".to_string()),
+            jwks_uri: format!("{}/certs", server.url()),
+            readiness_cache: ReadinessCache::default(),
+        }
+    }
+
+    async fn json_body(response: Response) -> Value {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn liveness_is_ok() {
+        assert_eq!(liveness().await.into_response().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn ready_when_all_dependencies_respond() {
+        let mut server = mockito::Server::new_async().await;
+        let argo = server
+            .mock("GET", "/api/v1/version")
+            .with_status(200)
+            .with_body(r#"{"version": "v4.0.6"}"#)
+            .create_async()
+            .await;
+        let kubernetes = server
+            .mock("GET", "/version")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "major": "1",
+                    "minor": "33",
+                    "gitVersion": "v1.33.0",
+                    "gitCommit": "",
+                    "gitTreeState": "clean",
+                    "buildDate": "2025-04-23T00:00:00Z",
+                    "goVersion": "go1.24.2",
+                    "compiler": "gc",
+                    "platform": "linux/amd64"
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let s3 = server
+            .mock("GET", "/test-bucket/.graph-proxy-readiness")
+            .match_query(mockito::Matcher::Any)
+            .with_status(404)
+            .with_body("<Error><Code>NoSuchKey</Code></Error>")
+            .create_async()
+            .await;
+        let jwks = server
+            .mock("GET", "/certs")
+            .with_status(200)
+            .with_body(r#"{"keys": []}"#)
+            .create_async()
+            .await;
+
+        let response = readiness(State(test_state(&server))).await.into_response();
+
+        argo.assert_async().await;
+        kubernetes.assert_async().await;
+        s3.assert_async().await;
+        jwks.assert_async().await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            json_body(response).await,
+            json!({
+                "ok": true,
+                "argo": {"ok": true},
+                "kubernetes": {"ok": true},
+                "s3": {"ok": true},
+                "jwks": {"ok": true}
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn unavailable_when_argo_is_down() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/v1/version")
+            .with_status(503)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/certs")
+            .with_status(200)
+            .with_body(r#"{"keys": []}"#)
+            .create_async()
+            .await;
+
+        let response = readiness(State(test_state(&server))).await.into_response();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = json_body(response).await;
+        assert_eq!(body["ok"], json!(false));
+        assert_eq!(body["argo"]["ok"], json!(false));
+        assert!(body["argo"]["error"].as_str().unwrap().contains("503"));
+        assert_eq!(body["jwks"], json!({"ok": true}));
+    }
+
+    #[tokio::test]
+    async fn ready_when_optional_dependencies_are_down() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/v1/version")
+            .with_status(200)
+            .with_body(r#"{"version": "v4.0.6"}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/version")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "major": "1",
+                    "minor": "33",
+                    "gitVersion": "v1.33.0",
+                    "gitCommit": "",
+                    "gitTreeState": "clean",
+                    "buildDate": "2025-04-23T00:00:00Z",
+                    "goVersion": "go1.24.2",
+                    "compiler": "gc",
+                    "platform": "linux/amd64"
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/test-bucket/.graph-proxy-readiness")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/certs")
+            .with_status(503)
+            .create_async()
+            .await;
+
+        let response = readiness(State(test_state(&server))).await.into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        assert_eq!(body["ok"], json!(true));
+        assert_eq!(body["s3"]["ok"], json!(false));
+        assert_eq!(body["jwks"]["ok"], json!(false));
+    }
+
+    #[tokio::test]
+    async fn readiness_is_cached() {
+        let mut server = mockito::Server::new_async().await;
+        let argo = server
+            .mock("GET", "/api/v1/version")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let state = test_state(&server);
+
+        let first = readiness(State(state.clone())).await.into_response();
+        let second = readiness(State(state)).await.into_response();
+
+        argo.assert_async().await;
+        assert_eq!(first.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(second.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -5,6 +5,8 @@
 
 /// GraphQL resolvers
 mod graphql;
+/// Liveness and readiness checks
+mod health;
 /// S3 client
 mod s3client;
 
@@ -14,37 +16,47 @@ mod metrics;
 /// Validate Bearer tokens
 mod validate_token;
 
+#[cfg(test)]
+mod test_kubeconfig;
 #[cfg(test)]
 mod test_oidc_server;
 
 use crate::{
     graphql::subscription_integration::GraphQLSubscription,
+    health::{liveness, readiness, HealthState},
     metrics::{Metrics, MetricsState},
-    validate_token::TokenValidator,
+    validate_token::{OidcIssuer, TokenValidator, ValidationMethod},
 };
 use async_graphql::{http::GraphiQLSource, SDLExportOptions};
 use axum::{
-    http::Uri,
+    http::{header, Uri},
     response::Html,
     routing::{get, get_service},
     Router,
 };
 use clap::{ArgAction, Parser};
-use graphql::{graphql_handler, root_schema_builder, RootSchema};
+use graphql::{
+    artifact_bundle_handler, artifact_handler, graphql_handler, root_schema_builder,
+    ArtifactRouterState, LoaderState, PersistedQueryArgs, QueryLimitArgs, RateLimiter, RoleArgs,
+    RootSchema, TemplateCache, VisitCache,
+};
 use regex::Regex;
 use reqwest::Method;
-use s3client::{Client, S3Bucket, S3ClientArgs};
+use s3client::{
+    ArtifactMetadataCache, ArtifactPreviewArgs, ArtifactUrlArgs, Client, S3Bucket, S3ClientArgs,
+};
 use std::{
     fs::File,
     io::Write,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 use telemetry::{setup_telemetry, TelemetryConfig};
 use tokio::net::TcpListener;
 use tower_http::cors::{AllowOrigin, CorsLayer};
-use tracing::{info, instrument, Level};
+use tracing::{info, instrument, warn, Level};
 
 use url::Url;
 
@@ -67,6 +79,13 @@ struct ServeArgs {
     /// The URL of the kubernetes API hosting the workflows
     #[arg(long, env = "KUBERNETES_API_URL")]
     kubernetes_api_url: Uri,
+    /// Serve workflow templates from a cache kept up to date by watching the Kubernetes API
+    #[arg(long, env = "TEMPLATE_CACHE", action = ArgAction::Set, default_value_t = true)]
+    template_cache: bool,
+    /// Look up the visits of users and instruments from a cache kept up to date by watching the
+    /// Kubernetes API
+    #[arg(long, env = "VISIT_CACHE", action = ArgAction::Set, default_value_t = true)]
+    visit_cache: bool,
     /// The host IP to bind the service to
     #[arg(long, env="HOST", default_value_t=IpAddr::V4(Ipv4Addr::UNSPECIFIED))]
     host: IpAddr,
@@ -88,6 +107,21 @@ struct ServeArgs {
     /// Configuration argument of the S3 client.
     #[command(flatten)]
     s3_client: S3ClientArgs,
+    /// Configuration of inline artifact previews.
+    #[command(flatten)]
+    artifact_preview: ArtifactPreviewArgs,
+    /// Configuration of artifact download URLs.
+    #[command(flatten)]
+    artifact_url: ArtifactUrlArgs,
+    /// Limits on the cost of GraphQL requests.
+    #[command(flatten)]
+    query_limits: QueryLimitArgs,
+    /// Configuration of persisted queries and the operation allowlist.
+    #[command(flatten)]
+    persisted_queries: PersistedQueryArgs,
+    /// Configuration of the roles derived from token claims.
+    #[command(flatten)]
+    roles: RoleArgs,
     /// The URL of the OIDC issuer (usually Keycloak)
     #[arg(
         long,
@@ -104,6 +138,18 @@ struct ServeArgs {
     /// Accepted audiences
     #[arg(long, env = "OIDC_AUDIENCES", value_delimiter = ',', default_value = "workflows-cluster,graph", num_args = 1..)]
     oidc_audiences: Vec<String>,
+    /// Further OIDC issuers to accept tokens from, each of the form `url=audience,audience`
+    #[arg(long, env = "OIDC_ADDITIONAL_ISSUERS", value_delimiter = ' ', num_args = 1..)]
+    oidc_additional_issuers: Vec<OidcIssuer>,
+    /// The interval, in seconds, at which the keys of each OIDC issuer are refreshed, disabled if zero
+    #[arg(long, env = "OIDC_JWKS_REFRESH_INTERVAL", default_value_t = 300)]
+    oidc_jwks_refresh_interval: u64,
+    /// The method used to validate bearer tokens
+    #[arg(long, env = "TOKEN_VALIDATION_METHOD", value_enum, default_value_t = ValidationMethod::Jwt)]
+    token_validation_method: ValidationMethod,
+    /// The maximum time, in seconds, for which a token introspection result is reused
+    #[arg(long, env = "OIDC_INTROSPECTION_CACHE_TTL", default_value_t = 60)]
+    oidc_introspection_cache_ttl: u64,
 }
 
 /// Arguments for producing the GraphQL schema
@@ -144,13 +190,8 @@ async fn main() {
 
             info!(?args, "Starting GraphQL Server");
             let s3_client = Client::from(args.s3_client);
-            let schema = root_schema_builder()
-                .data(ArgoServerUrl(args.argo_server_url))
-                .data(KubernetesApiUrl(args.kubernetes_api_url))
-                .data(s3_client)
-                .data(args.s3_bucket)
-                .data(metrics_state.clone())
-                .finish();
+            let argo_server_url = ArgoServerUrl(args.argo_server_url);
+            let artifact_metadata_cache = ArtifactMetadataCache::default();
             let token_validator = TokenValidator::new(
                 &args.oidc_issuer_url,
                 args.oidc_client_id,
@@ -158,13 +199,79 @@ async fn main() {
                 args.oidc_audiences,
             )
             .await
-            .expect("Failed to build OIDC token validator, Is Keycloak down?");
-            let router = setup_router(
+            .expect("Failed to build OIDC token validator, Is Keycloak down?")
+            .with_additional_issuers(&args.oidc_additional_issuers)
+            .await
+            .expect("Failed to add additional OIDC issuers")
+            .with_introspection_cache_ttl(Duration::from_secs(args.oidc_introspection_cache_ttl));
+            if args.oidc_jwks_refresh_interval > 0 {
+                token_validator
+                    .spawn_jwks_refresh(Duration::from_secs(args.oidc_jwks_refresh_interval));
+            }
+            let mut schema_builder = args
+                .persisted_queries
+                .apply(args.query_limits.apply(root_schema_builder()))
+                .expect("Failed to load operation allowlist");
+            if args.template_cache {
+                let template_cache = TemplateCache::spawn(&args.kubernetes_api_url)
+                    .await
+                    .expect("Failed to start workflow template cache");
+                schema_builder = schema_builder.data(template_cache);
+            }
+            if args.visit_cache {
+                let visit_cache = VisitCache::spawn(&args.kubernetes_api_url)
+                    .await
+                    .expect("Failed to start visit cache");
+                schema_builder = schema_builder.data(visit_cache);
+            }
+            let schema = schema_builder
+                .data(argo_server_url.clone())
+                .data(KubernetesApiUrl(args.kubernetes_api_url.clone()))
+                .data(s3_client.clone())
+                .data(args.s3_bucket.clone())
+                .data(args.artifact_preview)
+                .data(args.artifact_url)
+                .data(args.roles)
+                .data(artifact_metadata_cache.clone())
+                .data(metrics_state.clone())
+                .data(token_validator.clone())
+                .finish();
+            let health_state = HealthState {
+                argo_server_url: argo_server_url.clone(),
+                kubernetes_client: kubernetes_client(&args.kubernetes_api_url).await,
+                s3_client: s3_client.clone(),
+                s3_bucket: args.s3_bucket.clone(),
+                jwks_uri: token_validator.jwks_uri().to_string(),
+                readiness_cache: Default::default(),
+            };
+            let loader_state = LoaderState {
+                argo_server_url: argo_server_url.clone(),
+                s3_client: s3_client.clone(),
+                s3_bucket: args.s3_bucket.clone(),
+                artifact_metadata_cache,
+                metrics_state: metrics_state.clone(),
+            };
+            let artifact_state = ArtifactRouterState {
+                argo_server_url,
+                s3_client,
+                s3_bucket: args.s3_bucket,
+                token_validator: token_validator.clone(),
+                validation_method: args.token_validation_method,
+            };
+            let router_state = RouterState {
                 schema,
-                &args.prefix_path,
-                args.cors_allow,
                 metrics_state,
                 token_validator,
+                validation_method: args.token_validation_method,
+                loader_state,
+                rate_limiter: args.query_limits.rate_limiter(),
+            };
+            let router = setup_router(
+                router_state,
+                &args.prefix_path,
+                args.cors_allow,
+                artifact_state,
+                health_state,
             )
             .await
             .unwrap();
@@ -199,13 +306,16 @@ async fn main() {
 }
 
 /// Creates an [`axum::Router`] serving GraphiQL and sychronous GraphQL
-#[instrument(name = "graph_proxy_router_setup", skip(schema))]
+#[instrument(
+    name = "graph_proxy_router_setup",
+    skip(router_state, artifact_state, health_state)
+)]
 async fn setup_router(
-    schema: RootSchema,
+    router_state: RouterState,
     prefix_path: &str,
     cors_allow: Option<Vec<Regex>>,
-    metrics_state: MetricsState,
-    token_validator: TokenValidator,
+    artifact_state: ArtifactRouterState,
+    health_state: HealthState,
 ) -> anyhow::Result<Router> {
     info!("Setting up the router");
     let cors_origin = if let Some(cors_allow) = cors_allow {
@@ -223,6 +333,11 @@ async fn setup_router(
     };
 
     let socket_path = append_to_path(prefix_path, "/ws");
+    let artifact_bundle_path = append_to_path(prefix_path, "/artifacts/{visit}/{workflow_name}");
+    let artifact_path = append_to_path(
+        prefix_path,
+        "/artifacts/{visit}/{workflow_name}/{task_id}/{artifact_name}",
+    );
     Ok(Router::new()
         .route(
             prefix_path,
@@ -233,29 +348,54 @@ async fn setup_router(
                     .finish(),
             ))
             .post(graphql_handler)
-            .with_state(RouterState {
-                schema: schema.clone(),
-                metrics_state: metrics_state.clone(),
-                token_validator: token_validator.clone(),
-            }),
+            .with_state(router_state.clone()),
+        )
+        .route(
+            &artifact_path,
+            get(artifact_handler).with_state(artifact_state.clone()),
+        )
+        .route(
+            &artifact_bundle_path,
+            get(artifact_bundle_handler).with_state(artifact_state),
         )
+        .route("/healthz", get(liveness))
+        .route("/readyz", get(readiness).with_state(health_state))
         .route_service(
             &socket_path,
             get_service(GraphQLSubscription::new(
-                schema.clone(),
-                metrics_state.clone(),
-                Arc::new(token_validator),
+                router_state.schema.clone(),
+                router_state.metrics_state.clone(),
+                Arc::new(router_state.token_validator.clone()),
+                router_state.validation_method,
+                router_state.rate_limiter.clone(),
             )),
         )
-        .with_state(schema.clone())
+        .with_state(router_state.schema)
         .layer(
             CorsLayer::new()
                 .allow_methods([Method::GET, Method::POST])
                 .allow_headers(tower_http::cors::Any)
+                .expose_headers([
+                    header::ACCEPT_RANGES,
+                    header::CONTENT_DISPOSITION,
+                    header::CONTENT_RANGE,
+                ])
                 .allow_origin(cors_origin),
         ))
 }
 
+/// Creates a client of the Kubernetes API, used to check it can be reached
+async fn kubernetes_client(kubernetes_api_url: &Uri) -> Option<kube::Client> {
+    let mut config = kube::Config::infer()
+        .await
+        .inspect_err(|err| warn!("Could not infer Kubernetes config: {err}"))
+        .ok()?;
+    config.cluster_url = kubernetes_api_url.clone();
+    kube::Client::try_from(config)
+        .inspect_err(|err| warn!("Could not create Kubernetes client: {err}"))
+        .ok()
+}
+
 /// Append the websocket path to the graph prefix
 fn append_to_path(base: &str, extension: &str) -> String {
     let clean_base = base.trim_matches('/');
@@ -284,4 +424,10 @@ struct RouterState {
     metrics_state: MetricsState,
     /// Validate bearer tokens
     token_validator: TokenValidator,
+    /// The method used to validate bearer tokens
+    validation_method: ValidationMethod,
+    /// Builds the DataLoaders of each request
+    loader_state: LoaderState,
+    /// Limits the rate of requests from each subject, if configured
+    rate_limiter: Option<RateLimiter>,
 }
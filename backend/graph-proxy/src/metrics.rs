@@ -18,6 +18,8 @@ pub struct Metrics {
     pub query_depth: Histogram<u64>,
     /// Query complexity
     pub query_complexity: Histogram<u64>,
+    /// Number of distinct keys loaded by each DataLoader batch
+    pub dataloader_batch_size: Histogram<u64>,
 }
 
 impl Metrics {
@@ -51,12 +53,18 @@ impl Metrics {
             .with_description("GraphQL query complexity")
             .build();
 
+        let dataloader_batch_size = meter
+            .u64_histogram("graph_proxy_dataloader_batch_size")
+            .with_description("Number of upstream lookups made by each DataLoader batch")
+            .build();
+
         Metrics {
             total_requests,
             request_duration_ms,
             total_errors,
             query_depth,
             query_complexity,
+            dataloader_batch_size,
         }
     }
 }
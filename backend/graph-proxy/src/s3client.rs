@@ -1,7 +1,10 @@
 use aws_credential_types::{provider::SharedCredentialsProvider, Credentials};
 pub use aws_sdk_s3::{config::Region, Client};
-use clap::{ArgAction::SetTrue, Parser};
+use chrono::{DateTime, Utc};
+use clap::{ArgAction::SetTrue, Parser, ValueEnum};
 use derive_more::{Deref, FromStr, Into};
+use moka::future::Cache;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use url::Url;
 
 /// S3 bucket where the artifacts are stored
@@ -49,3 +52,131 @@ impl From<S3ClientArgs> for Client {
         Client::from_conf(config)
     }
 }
+
+/// Arguments for configuring inline previews of artifacts.
+#[derive(Debug, Parser, Clone, Copy)]
+pub struct ArtifactPreviewArgs {
+    /// The maximum number of bytes of text artifacts returned in a preview.
+    #[arg(long, env, default_value_t = 65536)]
+    pub artifact_preview_max_bytes: usize,
+    /// The maximum width and height, in pixels, of image artifact thumbnails.
+    #[arg(long, env, default_value_t = 256)]
+    pub artifact_thumbnail_size: u32,
+}
+
+impl Default for ArtifactPreviewArgs {
+    fn default() -> Self {
+        Self {
+            artifact_preview_max_bytes: 65536,
+            artifact_thumbnail_size: 256,
+        }
+    }
+}
+
+/// How download URLs are produced for artifacts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ArtifactUrlMode {
+    /// Presigned S3 URLs, fetched directly from the object store
+    #[default]
+    Presigned,
+    /// URLs of the artifact route of this service, which streams the artifact after checking the bearer token
+    Proxied,
+}
+
+/// Arguments for configuring artifact download and upload URLs.
+#[derive(Debug, Parser, Clone)]
+pub struct ArtifactUrlArgs {
+    /// Whether artifact URLs are presigned S3 URLs or are proxied through this service.
+    #[arg(long, env, value_enum, default_value_t = ArtifactUrlMode::Presigned)]
+    pub artifact_url_mode: ArtifactUrlMode,
+    /// The number of seconds for which presigned artifact download and upload URLs remain valid.
+    #[arg(long, env, default_value_t = 3600)]
+    pub artifact_presign_expiry: u64,
+    /// The externally reachable URL of this service's prefix path, used to build proxied artifact URLs.
+    #[arg(long, env, required_if_eq("artifact_url_mode", "proxied"))]
+    pub artifact_proxy_url: Option<Url>,
+    /// The key prefix below which uploaded input artifacts are stored, followed by the visit.
+    #[arg(long, env, default_value = "uploads")]
+    pub artifact_upload_prefix: String,
+}
+
+impl Default for ArtifactUrlArgs {
+    fn default() -> Self {
+        Self {
+            artifact_url_mode: ArtifactUrlMode::Presigned,
+            artifact_presign_expiry: 3600,
+            artifact_proxy_url: None,
+            artifact_upload_prefix: "uploads".to_string(),
+        }
+    }
+}
+
+/// Metadata of an artifact object, as returned by S3 `HeadObject`
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactMetadata {
+    /// The size of the object in bytes
+    pub size: Option<i64>,
+    /// The time at which the object was last written
+    pub last_modified: Option<DateTime<Utc>>,
+    /// The entity tag of the object
+    pub etag: Option<String>,
+    /// User defined metadata attached to the object
+    pub metadata: HashMap<String, String>,
+}
+
+/// A cache of [`ArtifactMetadata`], keyed by bucket and object key.
+///
+/// Concurrent lookups of the same object share a single `HeadObject` request.
+#[derive(Debug, Clone)]
+pub struct ArtifactMetadataCache(Cache<(String, String), Arc<ArtifactMetadata>>);
+
+impl Default for ArtifactMetadataCache {
+    fn default() -> Self {
+        Self(
+            Cache::builder()
+                .max_capacity(10_000)
+                .time_to_live(Duration::from_secs(3600))
+                .build(),
+        )
+    }
+}
+
+impl ArtifactMetadataCache {
+    /// Get the metadata of an object, fetching it from S3 if it is not cached
+    pub async fn get(
+        &self,
+        client: &Client,
+        bucket: &S3Bucket,
+        key: &str,
+    ) -> anyhow::Result<Arc<ArtifactMetadata>> {
+        self.0
+            .try_get_with(
+                (bucket.0.clone(), key.to_string()),
+                head_artifact(client, bucket, key),
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("{err}"))
+    }
+}
+
+/// Fetch the metadata of an object from S3
+pub async fn head_artifact(
+    client: &Client,
+    bucket: &S3Bucket,
+    key: &str,
+) -> anyhow::Result<Arc<ArtifactMetadata>> {
+    let object = client
+        .head_object()
+        .bucket(bucket.0.clone())
+        .key(key)
+        .send()
+        .await?;
+    Ok(Arc::new(ArtifactMetadata {
+        size: object.content_length,
+        last_modified: object
+            .last_modified
+            .and_then(|time| DateTime::from_timestamp(time.secs(), time.subsec_nanos())),
+        etag: object.e_tag,
+        metadata: object.metadata.unwrap_or_default(),
+    }))
+}
@@ -0,0 +1,47 @@
+use tokio::sync::{Mutex, MutexGuard};
+
+/// Serializes the tests which point `KUBECONFIG` at their own mock server
+static KUBECONFIG_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// A kubeconfig pointing Kubernetes clients at a mock server, set as `KUBECONFIG` for as long as
+/// it is held
+pub struct TestKubeconfig {
+    _file: tempfile::NamedTempFile,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl TestKubeconfig {
+    /// Waits for any other test using `KUBECONFIG` to finish, then points it at the server
+    pub async fn new(server_url: &str) -> anyhow::Result<Self> {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let guard = KUBECONFIG_LOCK.lock().await;
+
+        let kubeconfig = format!(
+            r#"
+apiVersion: v1
+kind: Config
+clusters:
+- cluster:
+    server: {server_url}
+  name: test
+contexts:
+- context:
+    cluster: test
+    user: test
+  name: test
+current-context: test
+users:
+- name: test
+  user: {{}}
+"#
+        );
+        let file = tempfile::NamedTempFile::new()?;
+        std::fs::write(file.path(), kubeconfig)?;
+        std::env::set_var("KUBECONFIG", file.path());
+
+        Ok(Self {
+            _file: file,
+            _guard: guard,
+        })
+    }
+}
@@ -1,6 +1,13 @@
 use axum::http::Uri;
 use axum_extra::headers::{authorization::Bearer, Authorization};
-use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use chrono::Utc;
+use jsonwebtoken::{
+    dangerous::insecure_decode,
+    decode, decode_header,
+    jwk::{Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use moka::{future::Cache, Expiry};
 use openidconnect::{
     core::CoreClient, AccessToken, ClientId, ClientSecret, EndpointMaybeSet, EndpointNotSet,
     EndpointSet, IntrospectionUrl, IssuerUrl, TokenIntrospectionResponse,
@@ -17,15 +24,24 @@ use openidconnect::{
     AdditionalProviderMetadata, ProviderMetadata,
 };
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use tracing::warn;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
 /// Method of token validation
 pub enum ValidationMethod {
     /// Use OIDC provider introspection end-point
-    #[allow(dead_code)]
     Introspection,
     /// Use local validation of the JWT
     Jwt,
+    /// Use local validation of the JWT, periodically checking it has not been revoked by introspection
+    JwtWithIntrospection,
 }
 
 /// Validated Tokens (including those found to be invalid)
@@ -56,6 +72,127 @@ impl ValidatedAuthToken {
     }
 }
 
+/// The minimum time between refetches of an issuer's keys prompted by an unknown key ID
+const JWKS_REFETCH_MIN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The default maximum time for which an introspection result is reused
+const DEFAULT_INTROSPECTION_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// The result of introspecting a token, with the time for which it may be reused
+#[derive(Debug, Clone)]
+struct CachedIntrospection {
+    /// Whether the OIDC issuer reported the token as active
+    active: bool,
+    /// The holder of the token, if the OIDC issuer reported them
+    holder: Option<TokenHolder>,
+    /// The time for which the result may be reused
+    time_to_live: Duration,
+}
+
+/// The user to whom a token was issued
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TokenHolder {
+    /// The OIDC subject of the user
+    pub sub: String,
+    /// The username of the user
+    pub preferred_username: String,
+}
+
+/// Expires each [`CachedIntrospection`] after its own time to live
+#[derive(Debug)]
+struct IntrospectionExpiry;
+
+impl Expiry<String, CachedIntrospection> for IntrospectionExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        value: &CachedIntrospection,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(value.time_to_live)
+    }
+}
+
+/// An additional OIDC issuer, with the audiences accepted from it
+#[derive(Debug, Clone)]
+pub struct OidcIssuer {
+    /// The URL of the OIDC issuer
+    pub url: Uri,
+    /// The audiences accepted in tokens from the issuer
+    pub audiences: Vec<String>,
+}
+
+impl FromStr for OidcIssuer {
+    type Err = anyhow::Error;
+
+    /// Parses an issuer of the form `url=audience,audience`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (url, audiences) = value
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected issuer of the form url=audience,audience"))?;
+        Ok(Self {
+            url: url.parse()?,
+            audiences: audiences.split(',').map(str::to_owned).collect(),
+        })
+    }
+}
+
+/// The keys and validation rules of a single OIDC issuer
+#[derive(Debug)]
+struct IssuerKeys {
+    /// The issuer identifier, as found in the `iss` claim
+    issuer: String,
+    /// The URL of the issuer's keys
+    jwks_uri: String,
+    /// The issuer's keys
+    json_web_key_set: RwLock<JwkSet>,
+    /// The time of the last refetch prompted by an unknown key ID
+    refetched_at: Mutex<Option<Instant>>,
+    /// JWT validation configuration
+    jwt_validation: Validation,
+}
+
+impl IssuerKeys {
+    /// Find a key by ID
+    fn key(&self, key_id: &str) -> Option<Jwk> {
+        self.json_web_key_set
+            .read()
+            .unwrap()
+            .keys
+            .iter()
+            .find(|key| key.common.key_id.as_deref() == Some(key_id))
+            .cloned()
+    }
+
+    /// Retrieve the issuer's current keys
+    async fn refresh(&self, http_client: &reqwest::Client) -> anyhow::Result<()> {
+        let json_web_key_set = fetch_json_web_key_set(http_client, &self.jwks_uri).await?;
+        *self.json_web_key_set.write().unwrap() = json_web_key_set;
+        Ok(())
+    }
+
+    /// Find a key by ID, refetching the issuer's keys if it is unknown and they were not
+    /// refetched within the [`JWKS_REFETCH_MIN_INTERVAL`]
+    async fn key_or_refetch(&self, http_client: &reqwest::Client, key_id: &str) -> Option<Jwk> {
+        if let Some(key) = self.key(key_id) {
+            return Some(key);
+        }
+        let mut refetched_at = self.refetched_at.lock().await;
+        // Another request may have refetched the keys while this one waited for the lock
+        if let Some(key) = self.key(key_id) {
+            return Some(key);
+        }
+        if refetched_at.is_some_and(|at| at.elapsed() < JWKS_REFETCH_MIN_INTERVAL) {
+            return None;
+        }
+        *refetched_at = Some(Instant::now());
+        if let Err(err) = self.refresh(http_client).await {
+            warn!("Failed to refetch keys of {}: {err}", self.issuer);
+        }
+        self.key(key_id)
+    }
+}
+
 /// Checks OIDC access_tokens are valid
 #[derive(Debug, Clone)]
 pub struct TokenValidator {
@@ -68,10 +205,14 @@ pub struct TokenValidator {
         EndpointMaybeSet,
         EndpointMaybeSet,
     >,
-    /// OIDC issuer keys
-    json_web_key_set: JwkSet,
-    /// JWT validation configuration
-    jwt_validation: Validation,
+    /// Client used to retrieve OIDC issuer metadata and keys
+    http_client: reqwest::Client,
+    /// The keys of each accepted issuer, starting with the issuer used for introspection
+    issuers: Vec<Arc<IssuerKeys>>,
+    /// Introspection results, keyed by the hash of the token
+    introspection_cache: Cache<String, CachedIntrospection>,
+    /// The maximum time for which an introspection result is reused
+    introspection_cache_ttl: Duration,
 }
 
 impl TokenValidator {
@@ -91,12 +232,7 @@ impl TokenValidator {
             .redirect(reqwest::redirect::Policy::none())
             .build()?;
         let oidc_issuer_url = oidc_issuer_url.into();
-        // Use OpenID Connect Discovery to fetch the provider metadata.
-        let provider_metadata = ProviderMetadataWithInstrospectionEndpoint::discover_async(
-            IssuerUrl::new(oidc_issuer_url.to_string())?,
-            &http_client,
-        )
-        .await?;
+        let provider_metadata = discover(&http_client, oidc_issuer_url).await?;
 
         let introspection_endpoint = &provider_metadata
             .additional_metadata()
@@ -107,25 +243,7 @@ impl TokenValidator {
             format!("{oidc_issuer_url}/protocol/openid-connect/token/introspect").to_string()
         };
 
-        let jwks_uri = provider_metadata.jwks_uri().to_string();
-
-        let json_web_key_set = http_client
-            .get(&jwks_uri)
-            .send()
-            .await
-            .and_then(|r| r.error_for_status())?
-            .json::<JwkSet>()
-            .await?;
-
-        let mut jwt_validation = Validation::new(Algorithm::RS256);
-        jwt_validation.set_issuer(&[provider_metadata.issuer()]);
-        let audiences: Vec<_> = audiences
-            .into_iter()
-            .map(|s| s.as_ref().trim().to_owned())
-            .filter(|s| !s.is_empty())
-            .collect();
-        jwt_validation.set_audience(&audiences);
-        jwt_validation.leeway = 60;
+        let issuer = issuer_keys(&http_client, &provider_metadata, audiences).await?;
 
         let client = CoreClient::from_provider_metadata(
             provider_metadata,
@@ -137,11 +255,71 @@ impl TokenValidator {
 
         Ok(Self {
             client,
-            json_web_key_set,
-            jwt_validation,
+            http_client,
+            issuers: vec![Arc::new(issuer)],
+            introspection_cache: introspection_cache(),
+            introspection_cache_ttl: DEFAULT_INTROSPECTION_CACHE_TTL,
         })
     }
 
+    /// Reuse introspection results for at most the given time, or until the token expires
+    ///
+    /// When validating with [`ValidationMethod::JwtWithIntrospection`] this is the interval at
+    /// which each token is re-introspected.
+    pub fn with_introspection_cache_ttl(mut self, introspection_cache_ttl: Duration) -> Self {
+        self.introspection_cache_ttl = introspection_cache_ttl;
+        self
+    }
+
+    /// Accept tokens from additional issuers, each with their own audiences
+    ///
+    /// Tokens from these issuers are only validated as JWTs, even when introspection is selected,
+    /// as introspection uses the first issuer.
+    pub async fn with_additional_issuers(
+        mut self,
+        additional_issuers: &[OidcIssuer],
+    ) -> anyhow::Result<Self> {
+        for additional_issuer in additional_issuers {
+            let provider_metadata = discover(&self.http_client, &additional_issuer.url).await?;
+            let issuer = issuer_keys(
+                &self.http_client,
+                &provider_metadata,
+                &additional_issuer.audiences,
+            )
+            .await?;
+            self.issuers.push(Arc::new(issuer));
+        }
+        Ok(self)
+    }
+
+    /// Periodically retrieve the keys of every issuer, so rotated keys are picked up
+    pub fn spawn_jwks_refresh(&self, interval: Duration) {
+        let http_client = self.http_client.clone();
+        let issuers = self.issuers.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                for issuer in &issuers {
+                    if let Err(err) = issuer.refresh(&http_client).await {
+                        warn!("Failed to refresh keys of {}: {err}", issuer.issuer);
+                    }
+                }
+            }
+        });
+    }
+
+    /// The URL from which the keys of the first issuer are retrieved
+    pub fn jwks_uri(&self) -> &str {
+        &self.issuers[0].jwks_uri
+    }
+
+    /// The identifier of the first issuer, as found in the `iss` claim of its tokens
+    pub fn primary_issuer(&self) -> &str {
+        &self.issuers[0].issuer
+    }
+
     /// Validate token using provided method
     pub async fn validate_token<T>(
         &self,
@@ -154,40 +332,98 @@ impl TokenValidator {
         let authorization_header = authorization_header.map(Into::into);
         match (authorization_header, method) {
             (Some(bearer_token), ValidationMethod::Introspection) => {
-                self.validate_token_with_introspection(bearer_token).await
+                if self.is_from_additional_issuer(bearer_token.token()) {
+                    self.validate_token_with_jwt(bearer_token).await
+                } else {
+                    self.validate_token_with_introspection(bearer_token).await
+                }
             }
             (Some(bearer_token), ValidationMethod::Jwt) => {
-                self.validate_token_with_jwt(bearer_token)
+                self.validate_token_with_jwt(bearer_token).await
+            }
+            (Some(bearer_token), ValidationMethod::JwtWithIntrospection) => {
+                match self.validate_token_with_jwt(bearer_token).await {
+                    ValidatedAuthToken::Valid(bearer_token)
+                        if !self.is_from_additional_issuer(bearer_token.token()) =>
+                    {
+                        self.validate_token_with_introspection(bearer_token).await
+                    }
+                    validated_token => validated_token,
+                }
             }
             (None, _) => ValidatedAuthToken::Missing,
         }
     }
 
     /// Validate token with OIDC issuer instrospection end-point
+    ///
+    /// Results are reused for the introspection cache TTL, or until the token expires.
     async fn validate_token_with_introspection(
         &self,
         token: Authorization<Bearer>,
     ) -> ValidatedAuthToken {
+        match self.introspect(token.token()).await {
+            Ok(introspection) if introspection.active => ValidatedAuthToken::Valid(token),
+            Ok(_) => ValidatedAuthToken::Invalid,
+            Err(err) => ValidatedAuthToken::Failed(err),
+        }
+    }
+
+    /// The holder of an active token, as reported by the OIDC issuer instrospection end-point
+    ///
+    /// Used to identify the holder of opaque tokens, whose claims cannot be decoded.
+    pub async fn introspected_holder(&self, token: &str) -> Option<TokenHolder> {
+        self.introspect(token)
+            .await
+            .ok()
+            .filter(|introspection| introspection.active)?
+            .holder
+    }
+
+    /// Introspect a token, reusing a cached result for the introspection cache TTL, or until the
+    /// token expires
+    async fn introspect(&self, token: &str) -> Result<CachedIntrospection, String> {
+        let token_hash = format!("{:x}", Sha256::digest(token.as_bytes()));
+        if let Some(cached) = self.introspection_cache.get(&token_hash).await {
+            return Ok(cached);
+        }
+
         let introspection_response = self
             .client
-            .introspect(&AccessToken::new(token.token().to_string()))
+            .introspect(&AccessToken::new(token.to_string()))
             .request_async(&reqwest::Client::new())
-            .await;
-
-        match introspection_response {
-            Ok(introspection_response) => {
-                if introspection_response.active() {
-                    ValidatedAuthToken::Valid(token)
-                } else {
-                    ValidatedAuthToken::Invalid
-                }
-            }
-            Err(err) => ValidatedAuthToken::Failed(err.to_string()),
+            .await
+            .map_err(|err| err.to_string())?;
+        let active = introspection_response.active();
+        let time_to_live = match introspection_response.exp() {
+            Some(exp) if active => (exp - Utc::now())
+                .to_std()
+                .unwrap_or_default()
+                .min(self.introspection_cache_ttl),
+            _ => self.introspection_cache_ttl,
+        };
+        let holder = introspection_response
+            .sub()
+            .zip(introspection_response.username())
+            .map(|(sub, username)| TokenHolder {
+                sub: sub.to_string(),
+                preferred_username: username.to_string(),
+            });
+        let introspection = CachedIntrospection {
+            active,
+            holder,
+            time_to_live,
+        };
+        if !time_to_live.is_zero() {
+            self.introspection_cache
+                .insert(token_hash, introspection.clone())
+                .await;
         }
+        Ok(introspection)
     }
 
     /// Validate token locally by inspecting JWT
-    fn validate_token_with_jwt(&self, token: Authorization<Bearer>) -> ValidatedAuthToken {
+    async fn validate_token_with_jwt(&self, token: Authorization<Bearer>) -> ValidatedAuthToken {
         let jwt = token.token();
 
         let key_id = match decode_header(jwt) {
@@ -200,26 +436,105 @@ impl TokenValidator {
             None => return ValidatedAuthToken::Invalid,
         };
 
-        let jwk = match self
-            .json_web_key_set
-            .keys
-            .iter()
-            .find(|k| k.common.key_id.as_deref() == Some(&key_id))
-        {
+        let issuer = match self.token_issuer(jwt) {
+            Some(issuer) => issuer,
+            None => return ValidatedAuthToken::Invalid,
+        };
+
+        let jwk = match issuer.key_or_refetch(&self.http_client, &key_id).await {
             Some(k) => k,
             None => return ValidatedAuthToken::Invalid,
         };
 
-        let decoding_key = match DecodingKey::from_jwk(jwk) {
+        let decoding_key = match DecodingKey::from_jwk(&jwk) {
             Ok(decoding_key) => decoding_key,
             Err(err) => return ValidatedAuthToken::Failed(format!("Unsupported JWK: {err}")),
         };
 
-        match decode::<Value>(jwt, &decoding_key, &self.jwt_validation) {
+        match decode::<Value>(jwt, &decoding_key, &issuer.jwt_validation) {
             Ok(_) => ValidatedAuthToken::Valid(token),
             Err(_) => ValidatedAuthToken::Invalid,
         }
     }
+
+    /// The accepted issuer named in the unverified `iss` claim of a token
+    fn token_issuer(&self, jwt: &str) -> Option<&IssuerKeys> {
+        let claims = insecure_decode::<Value>(jwt).ok()?.claims;
+        let issuer = claims["iss"].as_str()?;
+        self.issuers
+            .iter()
+            .find(|keys| keys.issuer == issuer)
+            .map(Arc::as_ref)
+    }
+
+    /// Whether the unverified `iss` claim of a token names an issuer other than the first, whose
+    /// tokens cannot be introspected
+    fn is_from_additional_issuer(&self, jwt: &str) -> bool {
+        self.token_issuer(jwt)
+            .is_some_and(|issuer| !std::ptr::eq(issuer, self.issuers[0].as_ref()))
+    }
+}
+
+/// Create an empty cache of introspection results
+fn introspection_cache() -> Cache<String, CachedIntrospection> {
+    Cache::builder()
+        .max_capacity(100_000)
+        .expire_after(IntrospectionExpiry)
+        .build()
+}
+
+/// Use OpenID Connect Discovery to fetch the provider metadata
+async fn discover(
+    http_client: &reqwest::Client,
+    oidc_issuer_url: &Uri,
+) -> anyhow::Result<ProviderMetadataWithInstrospectionEndpoint> {
+    Ok(ProviderMetadataWithInstrospectionEndpoint::discover_async(
+        IssuerUrl::new(oidc_issuer_url.to_string())?,
+        http_client,
+    )
+    .await?)
+}
+
+/// Retrieve the keys of an issuer and build the rules for validating its tokens
+async fn issuer_keys(
+    http_client: &reqwest::Client,
+    provider_metadata: &ProviderMetadataWithInstrospectionEndpoint,
+    audiences: impl IntoIterator<Item = impl AsRef<str>>,
+) -> anyhow::Result<IssuerKeys> {
+    let jwks_uri = provider_metadata.jwks_uri().to_string();
+    let json_web_key_set = fetch_json_web_key_set(http_client, &jwks_uri).await?;
+
+    let mut jwt_validation = Validation::new(Algorithm::RS256);
+    jwt_validation.set_issuer(&[provider_metadata.issuer()]);
+    let audiences: Vec<_> = audiences
+        .into_iter()
+        .map(|s| s.as_ref().trim().to_owned())
+        .filter(|s| !s.is_empty())
+        .collect();
+    jwt_validation.set_audience(&audiences);
+    jwt_validation.leeway = 60;
+
+    Ok(IssuerKeys {
+        issuer: provider_metadata.issuer().to_string(),
+        jwks_uri,
+        json_web_key_set: RwLock::new(json_web_key_set),
+        refetched_at: Mutex::new(None),
+        jwt_validation,
+    })
+}
+
+/// Retrieve a JSON Web Key Set
+async fn fetch_json_web_key_set(
+    http_client: &reqwest::Client,
+    jwks_uri: &str,
+) -> anyhow::Result<JwkSet> {
+    Ok(http_client
+        .get(jwks_uri)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())?
+        .json::<JwkSet>()
+        .await?)
 }
 
 /// Extend OIDC ProviderMetadata to retrieve introspection_endpoint
@@ -252,12 +567,360 @@ type ProviderMetadataWithInstrospectionEndpoint = ProviderMetadata<
 mod tests {
 
     use axum::http::Uri;
+    use axum_extra::headers::Authorization;
+    use jsonwebtoken::{encode, EncodingKey, Header};
     use rstest::rstest;
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
 
-    use super::{TokenValidator, ValidatedAuthToken, ValidationMethod};
+    use super::{OidcIssuer, TokenHolder, TokenValidator, ValidatedAuthToken, ValidationMethod};
 
     use crate::test_oidc_server::TestOidcServer;
 
+    fn test_asset(name: &str) -> String {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("test-assets");
+        path.push(name);
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    fn signing_jwk(key: &str) -> Value {
+        serde_json::from_str(&test_asset(&format!("oidc-signing-key-{key}.jwk.json"))).unwrap()
+    }
+
+    fn signed_token(key: &str, issuer: &str, audience: &str) -> Authorization<super::Bearer> {
+        signed_token_with_key_id(key, &format!("key-{key}"), issuer, audience)
+    }
+
+    fn signed_token_with_key_id(
+        key: &str,
+        key_id: &str,
+        issuer: &str,
+        audience: &str,
+    ) -> Authorization<super::Bearer> {
+        let mut header = Header::new(jsonwebtoken::Algorithm::RS256);
+        header.kid = Some(key_id.to_string());
+        let encoding_key = EncodingKey::from_rsa_pem(
+            test_asset(&format!("oidc-signing-key-{key}.pem")).as_bytes(),
+        )
+        .unwrap();
+        let claims = json!({
+            "iss": issuer,
+            "aud": audience,
+            "sub": "test-subject",
+            "exp": chrono::Utc::now().timestamp() + 300,
+        });
+        Authorization::bearer(&encode(&header, &claims, &encoding_key).unwrap()).unwrap()
+    }
+
+    /// Mocks the OIDC discovery end-point of a realm, returning its issuer URL
+    async fn mock_realm(server: &mut mockito::Server, realm: &str) -> String {
+        let issuer = format!("{}/realms/{realm}", server.url());
+        server
+            .mock(
+                "GET",
+                format!("/realms/{realm}/.well-known/openid-configuration").as_str(),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "issuer": issuer,
+                    "authorization_endpoint": format!("{issuer}/auth"),
+                    "jwks_uri": format!("{issuer}/certs"),
+                    "response_types_supported": ["code"],
+                    "subject_types_supported": ["public"],
+                    "id_token_signing_alg_values_supported": ["RS256"]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        issuer
+    }
+
+    async fn mock_keys(server: &mut mockito::Server, realm: &str, keys: &[&str]) -> mockito::Mock {
+        server
+            .mock("GET", format!("/realms/{realm}/certs").as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"keys": keys.iter().map(|key| signing_jwk(key)).collect::<Vec<_>>()})
+                    .to_string(),
+            )
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn rotated_keys_are_refetched_once() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let issuer = mock_realm(&mut server, "dls").await;
+        mock_keys(&mut server, "dls", &["a"]).await;
+        let token_validator =
+            TokenValidator::new(&issuer.parse::<Uri>()?, "graph", None, vec!["graph"]).await?;
+
+        let rotated_keys = mock_keys(&mut server, "dls", &["a", "b"]).await;
+        let rotated_token = signed_token("b", &issuer, "graph");
+        assert_eq!(
+            token_validator
+                .validate_token(Some(rotated_token.clone()), ValidationMethod::Jwt)
+                .await,
+            ValidatedAuthToken::Valid(rotated_token)
+        );
+        rotated_keys.assert_async().await;
+
+        let unknown_key_token = signed_token_with_key_id("b", "key-c", &issuer, "graph");
+        assert_eq!(
+            token_validator
+                .validate_token(Some(unknown_key_token), ValidationMethod::Jwt)
+                .await,
+            ValidatedAuthToken::Invalid
+        );
+        rotated_keys.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tokens_are_accepted_from_each_issuer_with_its_audiences() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let production = mock_realm(&mut server, "dls").await;
+        mock_keys(&mut server, "dls", &["a"]).await;
+        let service = mock_realm(&mut server, "service").await;
+        mock_keys(&mut server, "service", &["b"]).await;
+        let token_validator =
+            TokenValidator::new(&production.parse::<Uri>()?, "graph", None, vec!["graph"])
+                .await?
+                .with_additional_issuers(&[OidcIssuer {
+                    url: service.parse()?,
+                    audiences: vec!["workflows-service".to_string()],
+                }])
+                .await?;
+
+        for (token, valid) in [
+            (signed_token("a", &production, "graph"), true),
+            (signed_token("b", &service, "workflows-service"), true),
+            (signed_token("b", &service, "graph"), false),
+            (signed_token("a", &service, "workflows-service"), false),
+            (
+                signed_token("a", "https://elsewhere.example.com", "graph"),
+                false,
+            ),
+        ] {
+            let expected = if valid {
+                ValidatedAuthToken::Valid(token.clone())
+            } else {
+                ValidatedAuthToken::Invalid
+            };
+            assert_eq!(
+                token_validator
+                    .validate_token(Some(token), ValidationMethod::Jwt)
+                    .await,
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    async fn mock_introspection(
+        server: &mut mockito::Server,
+        realm: &str,
+        active: bool,
+        expires_in: i64,
+        hits: usize,
+    ) -> mockito::Mock {
+        mock_introspection_of(
+            server,
+            realm,
+            json!({
+                "active": active,
+                "exp": chrono::Utc::now().timestamp() + expires_in,
+            }),
+            hits,
+        )
+        .await
+    }
+
+    async fn mock_introspection_of(
+        server: &mut mockito::Server,
+        realm: &str,
+        response: Value,
+        hits: usize,
+    ) -> mockito::Mock {
+        server
+            .mock(
+                "POST",
+                format!("/realms/{realm}/protocol/openid-connect/token/introspect").as_str(),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response.to_string())
+            .expect(hits)
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn introspection_results_are_cached() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let issuer = mock_realm(&mut server, "dls").await;
+        mock_keys(&mut server, "dls", &["a"]).await;
+        let introspection = mock_introspection(&mut server, "dls", true, 300, 1).await;
+        let token_validator =
+            TokenValidator::new(&issuer.parse::<Uri>()?, "graph", None, vec!["graph"]).await?;
+
+        let token = signed_token("a", &issuer, "graph");
+        for _ in 0..2 {
+            assert_eq!(
+                token_validator
+                    .validate_token(Some(token.clone()), ValidationMethod::Introspection)
+                    .await,
+                ValidatedAuthToken::Valid(token.clone())
+            );
+        }
+        introspection.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn holder_of_opaque_token_is_introspected() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let issuer = mock_realm(&mut server, "dls").await;
+        mock_keys(&mut server, "dls", &["a"]).await;
+        let introspection = mock_introspection_of(
+            &mut server,
+            "dls",
+            json!({
+                "active": true,
+                "exp": chrono::Utc::now().timestamp() + 300,
+                "sub": "1234-abcd",
+                "username": "abc12345",
+            }),
+            1,
+        )
+        .await;
+        let token_validator =
+            TokenValidator::new(&issuer.parse::<Uri>()?, "graph", None, vec!["graph"]).await?;
+
+        let token = Authorization::bearer("opaque-token")?;
+        assert_eq!(
+            token_validator
+                .validate_token(Some(token.clone()), ValidationMethod::Introspection)
+                .await,
+            ValidatedAuthToken::Valid(token.clone())
+        );
+        assert_eq!(
+            token_validator.introspected_holder(token.token()).await,
+            Some(TokenHolder {
+                sub: "1234-abcd".to_string(),
+                preferred_username: "abc12345".to_string(),
+            })
+        );
+        introspection.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn revoked_token_is_rejected_with_jwt_and_introspection() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let issuer = mock_realm(&mut server, "dls").await;
+        mock_keys(&mut server, "dls", &["a"]).await;
+        let introspection = mock_introspection(&mut server, "dls", false, 300, 1).await;
+        let token_validator =
+            TokenValidator::new(&issuer.parse::<Uri>()?, "graph", None, vec!["graph"]).await?;
+
+        assert_eq!(
+            token_validator
+                .validate_token(
+                    Some(signed_token("a", &issuer, "graph")),
+                    ValidationMethod::JwtWithIntrospection
+                )
+                .await,
+            ValidatedAuthToken::Invalid
+        );
+        assert_eq!(
+            token_validator
+                .validate_token(
+                    Some(signed_token("a", &issuer, "elsewhere")),
+                    ValidationMethod::JwtWithIntrospection
+                )
+                .await,
+            ValidatedAuthToken::Invalid
+        );
+        introspection.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn introspection_is_not_reused_after_token_expiry() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let issuer = mock_realm(&mut server, "dls").await;
+        mock_keys(&mut server, "dls", &["a"]).await;
+        let introspection = mock_introspection(&mut server, "dls", true, 1, 2).await;
+        let token_validator =
+            TokenValidator::new(&issuer.parse::<Uri>()?, "graph", None, vec!["graph"])
+                .await?
+                .with_introspection_cache_ttl(std::time::Duration::from_secs(60));
+
+        let token = signed_token("a", &issuer, "graph");
+        for _ in 0..2 {
+            token_validator
+                .validate_token(Some(token.clone()), ValidationMethod::Introspection)
+                .await;
+            tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+        }
+        introspection.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(ValidationMethod::Introspection)]
+    #[case(ValidationMethod::JwtWithIntrospection)]
+    async fn additional_issuer_tokens_are_not_introspected(
+        #[case] method: ValidationMethod,
+    ) -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let production = mock_realm(&mut server, "dls").await;
+        mock_keys(&mut server, "dls", &["a"]).await;
+        let introspection = mock_introspection(&mut server, "dls", false, 300, 0).await;
+        let service = mock_realm(&mut server, "service").await;
+        mock_keys(&mut server, "service", &["b"]).await;
+        let token_validator =
+            TokenValidator::new(&production.parse::<Uri>()?, "graph", None, vec!["graph"])
+                .await?
+                .with_additional_issuers(&[OidcIssuer {
+                    url: service.parse()?,
+                    audiences: vec!["workflows-service".to_string()],
+                }])
+                .await?;
+
+        let token = signed_token("b", &service, "workflows-service");
+        assert_eq!(
+            token_validator
+                .validate_token(Some(token.clone()), method)
+                .await,
+            ValidatedAuthToken::Valid(token)
+        );
+        assert_eq!(
+            token_validator
+                .validate_token(Some(signed_token("b", &service, "graph")), method)
+                .await,
+            ValidatedAuthToken::Invalid
+        );
+        introspection.assert_async().await;
+        Ok(())
+    }
+
+    #[test]
+    fn additional_issuer_is_parsed() {
+        let issuer = "https://identity.example.com/realms/service=workflows,graph"
+            .parse::<OidcIssuer>()
+            .unwrap();
+        assert_eq!(issuer.url, "https://identity.example.com/realms/service");
+        assert_eq!(issuer.audiences, vec!["workflows", "graph"]);
+    }
+
     #[tokio::test]
     #[rstest]
     #[case(ValidationMethod::Introspection)]